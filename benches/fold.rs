@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lending_iter::prelude::*;
+
+const LEN: u64 = 100_000;
+
+fn manual_next(c: &mut Criterion) {
+    c.bench_function("map_filter_enumerate/next", |b| {
+        b.iter(|| {
+            let mut iter = (0..black_box(LEN))
+                .lending()
+                .map(|x: u64| x.wrapping_mul(31))
+                .filter(|x: &u64| !x.is_multiple_of(3))
+                .enumerate();
+            let mut sum = 0u64;
+            while let Some((i, x)) = iter.next() {
+                sum = sum.wrapping_add(i as u64 ^ x);
+            }
+            sum
+        })
+    });
+}
+
+fn internal_fold(c: &mut Criterion) {
+    c.bench_function("map_filter_enumerate/fold", |b| {
+        b.iter(|| {
+            (0..black_box(LEN))
+                .lending()
+                .map(|x: u64| x.wrapping_mul(31))
+                .filter(|x: &u64| !x.is_multiple_of(3))
+                .enumerate()
+                .fold(0u64, |sum, (i, x)| sum.wrapping_add(i as u64 ^ x))
+        })
+    });
+}
+
+fn chain_next(c: &mut Criterion) {
+    c.bench_function("chain_flat_map/next", |b| {
+        b.iter(|| {
+            let mut iter = (0..black_box(LEN / 100))
+                .lending()
+                .chain((0..black_box(LEN / 100)).lending())
+                .flat_map(|x: u64| (0..x % 100).lending());
+            let mut sum = 0u64;
+            while let Some(x) = iter.next() {
+                sum = sum.wrapping_add(x);
+            }
+            sum
+        })
+    });
+}
+
+fn chain_fold(c: &mut Criterion) {
+    c.bench_function("chain_flat_map/fold", |b| {
+        b.iter(|| {
+            (0..black_box(LEN / 100))
+                .lending()
+                .chain((0..black_box(LEN / 100)).lending())
+                .flat_map(|x: u64| (0..x % 100).lending())
+                .fold(0u64, |sum, x| sum.wrapping_add(x))
+        })
+    });
+}
+
+criterion_group!(benches, manual_next, internal_fold, chain_next, chain_fold);
+criterion_main!(benches);
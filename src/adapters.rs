@@ -3,7 +3,9 @@ use nougat::{apply, gat, Gat};
 #[gat(Item)]
 use crate::lending_iter::LendingIter;
 use crate::{
-    fn_traits::{Mapper, OptionMapper, Scanner},
+    constructors::windows::ArrayWindowBuf,
+    fallible::IntoResult,
+    fn_traits::{Mapper, OptionMapper, Predicate, ResultMapper, Scanner, WindowMapper},
     hkt::{extend_lifetime, HKT},
     lending_iter::LendedItem,
 };
@@ -29,6 +31,59 @@ impl<I: LendingIter> LendingIter for StepBy<I> {
             false => self.iter.nth(self.step - 1),
         }
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let mut accum = init;
+        if self.first {
+            self.first = false;
+            match self.iter.next() {
+                Some(x) => accum = f(accum, x)?,
+                None => return Ok(accum),
+            }
+        }
+        let step = self.step - 1;
+        let mut skip = step;
+        self.iter.try_fold(accum, |accum, x| match skip {
+            0 => {
+                skip = step;
+                f(accum, x)
+            }
+            _ => {
+                skip -= 1;
+                Ok(accum)
+            }
+        })
+    }
+
+    fn fold<B, G>(mut self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut accum = init;
+        if self.first {
+            match self.iter.next() {
+                Some(x) => accum = f(accum, x),
+                None => return accum,
+            }
+        }
+        let step = self.step - 1;
+        let mut skip = step;
+        self.iter.fold(accum, |accum, x| match skip {
+            0 => {
+                skip = step;
+                f(accum, x)
+            }
+            _ => {
+                skip -= 1;
+                accum
+            }
+        })
+    }
 }
 
 #[apply(Gat!)]
@@ -54,6 +109,34 @@ where
     fn next(&mut self) -> Option<Self::Item<'_>> {
         self.a.next().or_else(|| self.b.next())
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (a_lower.saturating_add(b_lower), upper)
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let accum = self.a.try_fold(init, &mut f)?;
+        self.b.try_fold(accum, f)
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let accum = self.a.fold(init, &mut f);
+        self.b.fold(accum, f)
+    }
 }
 
 pub struct Zip<I, J>
@@ -78,6 +161,16 @@ where
     fn next(&mut self) -> Option<Self::Item<'_>> {
         self.a.next().and_then(|ai| self.b.next().map(|bi| (ai, bi)))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (upper, None) | (None, upper) => upper,
+        };
+        (a_lower.min(b_lower), upper)
+    }
 }
 
 pub struct Map<I, F>
@@ -102,6 +195,28 @@ where
     fn next(&mut self) -> Option<Self::Item<'_>> {
         self.iter.next().map(|i| self.fun.call(i))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let fun = &mut self.fun;
+        self.iter.try_fold(init, |accum, x| f(accum, fun.call(x)))
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut fun = self.fun;
+        self.iter.fold(init, |accum, x| f(accum, fun.call(x)))
+    }
 }
 
 pub struct Filter<I, F>
@@ -124,7 +239,40 @@ where
         Self: 'a;
 
     fn next(&mut self) -> Option<Self::Item<'_>> {
-        self.iter.next().filter(&mut self.filter)
+        loop {
+            // SAFETY:
+            //  The Item<'a> we are matching on is either dropped or returned by
+            //  the end of this match block, so it never escapes its actual lifetime
+            match unsafe { extend_lifetime::<HKT!(Option<LendedItem<'_, I>>)>(self.iter.next()) } {
+                None => return None,
+                Some(item) if (self.filter)(&item) => return Some(item),
+                Some(_) => continue,
+            }
+        }
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let filter = &mut self.filter;
+        self.iter.try_fold(init, |accum, x| match filter(&x) {
+            true => f(accum, x),
+            false => Ok(accum),
+        })
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut filter = self.filter;
+        self.iter.fold(init, |accum, x| match filter(&x) {
+            true => f(accum, x),
+            false => accum,
+        })
     }
 }
 
@@ -161,6 +309,30 @@ where
             }
         }
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let filter = &mut self.filter;
+        self.iter.try_fold(init, |accum, x| match filter.call(x) {
+            Some(v) => f(accum, v),
+            None => Ok(accum),
+        })
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut filter = self.filter;
+        self.iter.fold(init, |accum, x| match filter.call(x) {
+            Some(v) => f(accum, v),
+            None => accum,
+        })
+    }
 }
 
 pub struct Enumerate<I: LendingIter> {
@@ -179,6 +351,36 @@ impl<I: LendingIter> LendingIter for Enumerate<I> {
         self.count += 1;
         item
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let count = &mut self.count;
+        self.iter.try_fold(init, |accum, x| {
+            let accum = f(accum, (*count, x));
+            *count += 1;
+            accum
+        })
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut count = self.count;
+        self.iter.fold(init, |accum, x| {
+            let accum = f(accum, (count, x));
+            count += 1;
+            accum
+        })
+    }
 }
 
 pub struct SkipWhile<I, F>
@@ -217,6 +419,36 @@ where
             }
         }
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let accum = match self.done {
+            true => init,
+            false => match self.next() {
+                Some(x) => f(init, x)?,
+                None => return Ok(init),
+            },
+        };
+        self.iter.try_fold(accum, f)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let accum = match self.done {
+            true => init,
+            false => match self.next() {
+                Some(x) => f(init, x),
+                None => return init,
+            },
+        };
+        self.iter.fold(accum, f)
+    }
 }
 
 pub struct TakeWhile<I, F>
@@ -245,6 +477,35 @@ where
             _ => None,
         }
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let pred = &mut self.pred;
+        self.iter
+            .try_fold(init, |accum, x| match pred(&x) {
+                true => f(accum, x).map_err(Err),
+                false => Err(Ok(accum)),
+            })
+            .or_else(|r| r)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let pred = &mut self.pred;
+        let result = self.iter.try_fold(init, |accum, x| match pred(&x) {
+            true => Ok(f(accum, x)),
+            false => Err(accum),
+        });
+        match result {
+            Ok(accum) | Err(accum) => accum,
+        }
+    }
 }
 
 pub struct MapWhile<I, F>
@@ -269,6 +530,35 @@ where
     fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
         self.iter.next().and_then(|i| self.pred.call(i))
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let pred = &mut self.pred;
+        self.iter
+            .try_fold(init, |accum, x| match pred.call(x) {
+                Some(v) => f(accum, v).map_err(Err),
+                None => Err(Ok(accum)),
+            })
+            .or_else(|r| r)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let pred = &mut self.pred;
+        let result = self.iter.try_fold(init, |accum, x| match pred.call(x) {
+            Some(v) => Ok(f(accum, v)),
+            None => Err(accum),
+        });
+        match result {
+            Ok(accum) | Err(accum) => accum,
+        }
+    }
 }
 
 pub struct Skip<I: LendingIter> {
@@ -291,6 +581,39 @@ impl<I: LendingIter> LendingIter for Skip<I> {
         }
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_sub(self.n),
+            upper.map(|upper| upper.saturating_sub(self.n)),
+        )
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        if self.n > 0 {
+            let n = core::mem::take(&mut self.n);
+            if self.iter.nth(n - 1).is_none() {
+                return Ok(init);
+            }
+        }
+        self.iter.try_fold(init, f)
+    }
+
+    fn fold<B, F>(mut self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        if self.n > 0 && self.iter.nth(self.n - 1).is_none() {
+            return init;
+        }
+        self.iter.fold(init, f)
+    }
 }
 
 pub struct Take<I: LendingIter> {
@@ -313,6 +636,55 @@ impl<I: LendingIter> LendingIter for Take<I> {
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.min(self.n),
+            Some(upper.map_or(self.n, |upper| upper.min(self.n))),
+        )
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        if self.n == 0 {
+            return Ok(init);
+        }
+        let n = &mut self.n;
+        self.iter
+            .try_fold(init, |accum, x| {
+                *n -= 1;
+                match (f(accum, x), *n) {
+                    (Ok(accum), 0) => Err(Ok(accum)),
+                    (accum, _) => accum.map_err(Err),
+                }
+            })
+            .or_else(|r| r)
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        if self.n == 0 {
+            return init;
+        }
+        let mut n = self.n;
+        let result = self.iter.try_fold(init, |accum, x| {
+            n -= 1;
+            match (f(accum, x), n) {
+                (accum, 0) => Err(accum),
+                (accum, _) => Ok(accum),
+            }
+        });
+        match result {
+            Ok(accum) | Err(accum) => accum,
+        }
+    }
 }
 
 pub struct Scan<I, S, F>
@@ -381,6 +753,40 @@ where
             }
         }
     }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let mut accum = init;
+        if let Some(it) = &mut self.curr {
+            accum = it.try_fold(accum, &mut f)?;
+            self.curr = None;
+        }
+        let (fun, curr) = (&mut self.fun, &mut self.curr);
+        self.iter.try_fold(accum, |accum, x| {
+            let mut it = fun(x);
+            let result = it.try_fold(accum, &mut f);
+            if result.is_err() {
+                *curr = Some(it);
+            }
+            result
+        })
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let accum = match self.curr {
+            Some(it) => it.fold(init, &mut f),
+            None => init,
+        };
+        let mut fun = self.fun;
+        self.iter.fold(accum, |accum, x| fun(x).fold(accum, &mut f))
+    }
 }
 
 #[apply(Gat!)]
@@ -422,6 +828,38 @@ where
             }
         }
     }
+
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let mut accum = init;
+        if let Some(it) = &mut self.curr {
+            accum = it.try_fold(accum, &mut f)?;
+            self.curr = None;
+        }
+        let curr = &mut self.curr;
+        self.iter.try_fold(accum, |accum, mut it| {
+            let result = it.try_fold(accum, &mut f);
+            if result.is_err() {
+                *curr = Some(it);
+            }
+            result
+        })
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let accum = match self.curr {
+            Some(it) => it.fold(init, &mut f),
+            None => init,
+        };
+        self.iter.fold(accum, |accum, it| it.fold(accum, &mut f))
+    }
 }
 
 pub struct Fuse<I: LendingIter> {
@@ -444,6 +882,37 @@ impl<I: LendingIter> LendingIter for Fuse<I> {
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.avail {
+            true => self.iter.size_hint(),
+            false => (0, Some(0)),
+        }
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        if !self.avail {
+            return Ok(init);
+        }
+        let accum = self.iter.try_fold(init, f)?;
+        self.avail = false;
+        Ok(accum)
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        match self.avail {
+            true => self.iter.fold(init, f),
+            false => init,
+        }
+    }
 }
 
 pub struct Inspect<I, F>
@@ -464,9 +933,805 @@ where
     type Item<'a> = LendedItem<'a, I> where Self: 'a;
 
     fn next(&mut self) -> Option<Self::Item<'_>> {
-        self.iter.next().map(|i| {
-            (self.fun)(&i);
-            i
+        self.iter.next().inspect(|i| (self.fun)(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let fun = &mut self.fun;
+        self.iter.try_fold(init, |accum, x| {
+            fun(&x);
+            f(accum, x)
+        })
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut fun = self.fun;
+        self.iter.fold(init, |accum, x| {
+            fun(&x);
+            f(accum, x)
+        })
+    }
+}
+
+pub struct MapWindows<I, F, const N: usize>
+where
+    F: WindowMapper<I, N>,
+{
+    pub(crate) iter: I,
+    pub(crate) fun: F,
+    pub(crate) window: ArrayWindowBuf<F::Item, N>,
+}
+
+#[gat]
+impl<I, F, T, R, const N: usize> LendingIter for MapWindows<I, F, N>
+where
+    I: for<'b> LendingIter<Item<'b> = T>,
+    T: Clone,
+    F: FnMut(&[T; N]) -> R,
+{
+    type Item<'a> = R where Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let iter = &mut self.iter;
+        let window = self.window.advance(|| iter.next())?;
+        Some((self.fun)(window))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.size_hint(self.iter.size_hint())
+    }
+}
+
+pub struct MapOk<I, F>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult,
+{
+    pub(crate) iter: I,
+    pub(crate) fun: F,
+}
+
+#[gat]
+impl<I, F> LendingIter for MapOk<I, F>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult,
+    F: for<'b> Mapper<'b, <LendedItem<'b, I> as IntoResult>::Ok>,
+{
+    type Item<'a> = Result<
+        <F as Mapper<'a, <LendedItem<'a, I> as IntoResult>::Ok>>::Output,
+        <LendedItem<'a, I> as IntoResult>::Err,
+    >
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        self.iter
+            .next()
+            .map(|i| i.into_result().map(|v| self.fun.call(v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let fun = &mut self.fun;
+        self.iter
+            .try_fold(init, |accum, x| f(accum, x.into_result().map(|v| fun.call(v))))
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut fun = self.fun;
+        self.iter
+            .fold(init, |accum, x| f(accum, x.into_result().map(|v| fun.call(v))))
+    }
+}
+
+pub struct FilterOk<I, F>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult,
+{
+    pub(crate) iter: I,
+    pub(crate) filter: F,
+}
+
+#[gat]
+impl<I, F> LendingIter for FilterOk<I, F>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult,
+    F: for<'b> Predicate<'b, <LendedItem<'b, I> as IntoResult>::Ok>,
+{
+    type Item<'a> = Result<
+        <LendedItem<'a, I> as IntoResult>::Ok,
+        <LendedItem<'a, I> as IntoResult>::Err,
+    >
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        loop {
+            // SAFETY:
+            //  The Item<'a> we are matching on is either dropped or returned by
+            //  the end of this match block, so it never escapes its actual lifetime
+            match unsafe { extend_lifetime::<HKT!(Option<LendedItem<'_, I>>)>(self.iter.next()) } {
+                None => return None,
+                Some(item) => match item.into_result() {
+                    Ok(v) if !self.filter.call(&v) => continue,
+                    result => return Some(result),
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let filter = &mut self.filter;
+        self.iter.try_fold(init, |accum, x| match x.into_result() {
+            Ok(v) if !filter.call(&v) => Ok(accum),
+            result => f(accum, result),
+        })
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut filter = self.filter;
+        self.iter.fold(init, |accum, x| match x.into_result() {
+            Ok(v) if !filter.call(&v) => accum,
+            result => f(accum, result),
+        })
+    }
+}
+
+pub struct FilterMapOk<I, F>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult,
+{
+    pub(crate) iter: I,
+    pub(crate) filter: F,
+}
+
+#[gat]
+impl<I, F> LendingIter for FilterMapOk<I, F>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult,
+    F: for<'b> OptionMapper<'b, <LendedItem<'b, I> as IntoResult>::Ok>,
+{
+    type Item<'a> = Result<
+        <F as OptionMapper<'a, <LendedItem<'a, I> as IntoResult>::Ok>>::Output,
+        <LendedItem<'a, I> as IntoResult>::Err,
+    >
+        where
+            Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        loop {
+            // SAFETY:
+            //  The Item<'a> we are matching on is either dropped or returned by
+            //  the end of this match block, so it never escapes its actual lifetime
+            match unsafe { extend_lifetime::<HKT!(Option<LendedItem<'_, I>>)>(self.iter.next()) } {
+                None => return None,
+                Some(item) => match item.into_result() {
+                    Ok(v) => match self.filter.call(v) {
+                        None => continue,
+                        Some(v) => return Some(Ok(v)),
+                    },
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let filter = &mut self.filter;
+        self.iter.try_fold(init, |accum, x| match x.into_result() {
+            Ok(v) => match filter.call(v) {
+                Some(v) => f(accum, Ok(v)),
+                None => Ok(accum),
+            },
+            Err(e) => f(accum, Err(e)),
         })
     }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut filter = self.filter;
+        self.iter.fold(init, |accum, x| match x.into_result() {
+            Ok(v) => match filter.call(v) {
+                Some(v) => f(accum, Ok(v)),
+                None => accum,
+            },
+            Err(e) => f(accum, Err(e)),
+        })
+    }
+}
+
+pub struct AndThenOk<I, F>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult,
+{
+    pub(crate) iter: I,
+    pub(crate) fun: F,
+}
+
+#[gat]
+impl<I, F> LendingIter for AndThenOk<I, F>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult,
+    F: for<'b> ResultMapper<
+        'b,
+        <LendedItem<'b, I> as IntoResult>::Ok,
+        <LendedItem<'b, I> as IntoResult>::Err,
+    >,
+{
+    type Item<'a> = Result<
+        <F as ResultMapper<
+            'a,
+            <LendedItem<'a, I> as IntoResult>::Ok,
+            <LendedItem<'a, I> as IntoResult>::Err,
+        >>::Output,
+        <LendedItem<'a, I> as IntoResult>::Err,
+    >
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        self.iter
+            .next()
+            .map(|i| i.into_result().and_then(|v| self.fun.call(v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, G>(&mut self, init: B, mut f: G) -> Result<B, E>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        let fun = &mut self.fun;
+        self.iter
+            .try_fold(init, |accum, x| f(accum, x.into_result().and_then(|v| fun.call(v))))
+    }
+
+    fn fold<B, G>(self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        let mut fun = self.fun;
+        self.iter
+            .fold(init, |accum, x| f(accum, x.into_result().and_then(|v| fun.call(v))))
+    }
+}
+
+/// Iterator over the `Ok` values of a [`LendingIter`] of `Result`s, handed to the closure
+/// passed to [`LendingIter::process_results`]. Stops at the first `Err`, which is stored
+/// and returned by [`LendingIter::process_results`].
+pub struct ProcessResults<'r, I, E>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult<Err = E>,
+{
+    pub(crate) iter: I,
+    pub(crate) error: &'r mut Result<(), E>,
+}
+
+#[gat]
+impl<'r, I, E> LendingIter for ProcessResults<'r, I, E>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult<Err = E>,
+{
+    type Item<'a> = <LendedItem<'a, I> as IntoResult>::Ok
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.error.is_err() {
+            return None;
+        }
+        match self.iter.next()?.into_result() {
+            Ok(v) => Some(v),
+            Err(e) => {
+                *self.error = Err(e);
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.error {
+            Ok(()) => (0, self.iter.size_hint().1),
+            Err(_) => (0, Some(0)),
+        }
+    }
+
+    fn try_fold<B, R, G>(&mut self, init: B, mut f: G) -> Result<B, R>
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, R>,
+    {
+        if self.error.is_err() {
+            return Ok(init);
+        }
+        let error = &mut *self.error;
+        self.iter
+            .try_fold(init, |accum, x| match x.into_result() {
+                Ok(v) => f(accum, v).map_err(Err),
+                Err(e) => {
+                    *error = Err(e);
+                    Err(Ok(accum))
+                }
+            })
+            .or_else(|r| r)
+    }
+
+    fn fold<B, G>(mut self, init: B, mut f: G) -> B
+    where
+        Self: Sized,
+        G: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        if self.error.is_err() {
+            return init;
+        }
+        let error = &mut *self.error;
+        let result = self.iter.try_fold(init, |accum, x| match x.into_result() {
+            Ok(v) => Ok(f(accum, v)),
+            Err(e) => {
+                *error = Err(e);
+                Err(accum)
+            }
+        });
+        match result {
+            Ok(accum) | Err(accum) => accum,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, fmt::Debug};
+    use std::rc::Rc;
+
+    use nougat::{apply, gat, Gat};
+
+    #[gat(Item)]
+    use crate::lending_iter::LendingIter;
+    use crate::prelude::IntoLending;
+
+    /// Items lent by `try_fold` when it is stopped after every `stop_every` items and
+    /// resumed, calling `next` in between when `next_between` is set.
+    #[apply(Gat!)]
+    fn resumed<I, T>(mut iter: I, stop_every: usize, next_between: bool) -> Vec<T>
+    where
+        I: for<'a> LendingIter<Item<'a> = T>,
+    {
+        let mut items = Vec::new();
+        loop {
+            let mut left = stop_every;
+            let stopped = iter.try_fold((), |(), x| {
+                items.push(x);
+                left -= 1;
+                match left {
+                    0 => Err(()),
+                    _ => Ok(()),
+                }
+            });
+            if stopped.is_ok() {
+                return items;
+            }
+            if next_between {
+                match iter.next() {
+                    Some(x) => items.push(x),
+                    None => return items,
+                }
+            }
+        }
+    }
+
+    /// Checks that `next`, `fold`, `try_fold` resumed after stopping, and the consumers
+    /// built on them all agree on the items lent by the iterators made by `make`.
+    #[apply(Gat!)]
+    fn check<I, T, F>(make: F, expected: &[T])
+    where
+        I: for<'a> LendingIter<Item<'a> = T>,
+        T: PartialEq + Debug,
+        F: Fn() -> I,
+    {
+        let mut iter = make();
+        let mut by_next = Vec::new();
+        while let Some(x) = iter.next() {
+            by_next.push(x);
+        }
+        assert_eq!(by_next, expected, "next");
+
+        let by_fold = make().fold(Vec::new(), |mut items, x| {
+            items.push(x);
+            items
+        });
+        assert_eq!(by_fold, expected, "fold");
+
+        for stop_every in 1..=3 {
+            for next_between in [false, true] {
+                let items = resumed(make(), stop_every, next_between);
+                assert_eq!(items, expected, "try_fold stopped every {stop_every} items");
+            }
+        }
+
+        assert_eq!(make().count(), expected.len(), "count");
+        let mut count = 0;
+        make().for_each(|_| count += 1);
+        assert_eq!(count, expected.len(), "for_each");
+        assert_eq!(make().try_for_each(|_| Ok::<_, ()>(())), Ok(()), "try_for_each");
+        assert!(make().all(|_| true), "all");
+        assert!(!make().any(|_| false), "any");
+        if let Some(last) = expected.last() {
+            let first = expected.iter().position(|x| x == last);
+            assert_eq!(make().position(|x| x == *last), first, "position");
+            assert!(make().any(|x| x == *last), "any");
+            assert!(!make().all(|x| x != *last), "all");
+        }
+        assert_eq!(make().position(|_| false), None, "position");
+    }
+
+    #[test]
+    fn step_by() {
+        check(|| (0..10).lending().step_by(3), &[0, 3, 6, 9]);
+        check(|| (0..10).lending().step_by(1), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        check(|| (0..0).lending().step_by(2), &[]);
+    }
+
+    #[test]
+    fn chain() {
+        check(|| (0..3).lending().chain((5..7).lending()), &[0, 1, 2, 5, 6]);
+        check(|| (0..0).lending().chain((5..7).lending()), &[5, 6]);
+    }
+
+    #[test]
+    fn zip() {
+        check(|| (0..3).lending().zip((5..9).lending()), &[(0, 5), (1, 6), (2, 7)]);
+    }
+
+    #[test]
+    fn map() {
+        check(|| (0..4).lending().map(|x: u32| x * 2), &[0, 2, 4, 6]);
+        let windows = || {
+            [1, 2, 3, 4]
+                .into_iter()
+                .array_windows_lending::<2>()
+                .map(|w: &[u32; 2]| w[0] * 10 + w[1])
+        };
+        check(windows, &[12, 23, 34]);
+    }
+
+    #[test]
+    fn filter() {
+        check(|| (0..10).lending().filter(|x: &u32| x % 3 == 1), &[1, 4, 7]);
+        check(|| (0..10).lending().filter(|_: &u32| false), &[]);
+    }
+
+    #[test]
+    fn filter_next_skips_rejected_items() {
+        let mut odd = [2, 4, 1, 6, 3].into_iter().lending().filter(|x: &u32| x % 2 == 1);
+        assert_eq!(odd.next(), Some(1));
+        assert_eq!(odd.next(), Some(3));
+        assert_eq!(odd.next(), None);
+    }
+
+    #[test]
+    fn filter_map() {
+        let halves = || (0..7).lending().filter_map(|x: u32| x.is_multiple_of(2).then_some(x / 2));
+        check(halves, &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn enumerate() {
+        check(|| (5..8).lending().enumerate(), &[(0, 5), (1, 6), (2, 7)]);
+    }
+
+    #[test]
+    fn skip_while() {
+        check(|| [1, 2, 5, 1, 6].into_iter().lending().skip_while(|x: &u32| *x < 3), &[5, 1, 6]);
+        check(|| (0..3).lending().skip_while(|_: &u32| true), &[]);
+    }
+
+    #[test]
+    fn take_while() {
+        check(|| [1, 2, 5, 1, 6].into_iter().lending().take_while(|x: &u32| *x < 3), &[1, 2]);
+        check(|| (0..3).lending().take_while(|_: &u32| true), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn map_while() {
+        let small = || {
+            [1, 2, 5, 1]
+                .into_iter()
+                .lending()
+                .map_while::<u32, _>(|x: u32| (x < 3).then_some(x * 10))
+        };
+        check(small, &[10, 20]);
+    }
+
+    #[test]
+    fn skip() {
+        check(|| (0..5).lending().skip(2), &[2, 3, 4]);
+        check(|| (0..5).lending().skip(7), &[]);
+    }
+
+    #[test]
+    fn take() {
+        check(|| (0..5).lending().take(2), &[0, 1]);
+        check(|| (0..5).lending().take(7), &[0, 1, 2, 3, 4]);
+        check(|| (0..5).lending().take(0), &[]);
+    }
+
+    #[test]
+    fn scan() {
+        let sums = || {
+            (1..5).lending().scan(0, |sum: &mut u32, x: u32| {
+                *sum += x;
+                Some(*sum)
+            })
+        };
+        check(sums, &[1, 3, 6, 10]);
+    }
+
+    #[test]
+    fn flat_map() {
+        let pairs = || {
+            (1..5).lending().flat_map(|n: u32| {
+                (0..n)
+                    .array_windows_lending::<2>()
+                    .map(|w: &[u32; 2]| w[0] * 10 + w[1])
+            })
+        };
+        check(pairs, &[1, 1, 12, 1, 12, 23]);
+    }
+
+    #[test]
+    fn flatten() {
+        let ranges = || (0..4).map(|n| (0..n).lending()).lending().flatten();
+        check(ranges, &[0, 0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn fuse() {
+        check(|| (0..3).lending().fuse(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn inspect() {
+        let mut seen = Vec::new();
+        (0..3).lending().inspect(|x: &u32| seen.push(*x)).for_each(|_| {});
+        assert_eq!(seen, [0, 1, 2]);
+        check(|| (0..3).lending().inspect(|_: &u32| {}), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn nested_adapters() {
+        let nested = || {
+            (0..20)
+                .lending()
+                .skip(1)
+                .step_by(2)
+                .filter(|x: &u32| !x.is_multiple_of(3))
+                .enumerate()
+                .take(4)
+                .chain((100..102).lending().enumerate())
+        };
+        check(nested, &[(0, 1), (1, 5), (2, 7), (3, 11), (0, 100), (1, 101)]);
+    }
+
+    /// Lends mutable pairs of a buffer, or the index of the pair as an error for the pairs
+    /// starting with zero, and counts the pairs pulled.
+    struct Pairs {
+        buf: Vec<u32>,
+        pos: usize,
+        pulled: Rc<Cell<usize>>,
+    }
+
+    fn pairs(buf: &[u32]) -> Pairs {
+        Pairs {
+            buf: buf.to_vec(),
+            pos: 0,
+            pulled: Rc::default(),
+        }
+    }
+
+    #[gat]
+    impl LendingIter for Pairs {
+        type Item<'a> = Result<&'a mut [u32], usize>
+            where
+                Self: 'a;
+
+        fn next(&mut self) -> Option<Self::Item<'_>> {
+            let pair = self.buf.get_mut(self.pos..self.pos + 2)?;
+            self.pos += 2;
+            self.pulled.set(self.pulled.get() + 1);
+            match pair[0] {
+                0 => Some(Err(self.pos / 2 - 1)),
+                _ => Some(Ok(pair)),
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let left = (self.buf.len() - self.pos) / 2;
+            (left, Some(left))
+        }
+    }
+
+    #[test]
+    fn map_ok() {
+        let sums = || pairs(&[1, 2, 0, 4, 5, 6]).map_ok(|pair: &mut [u32]| pair[0] + pair[1]);
+        check(sums, &[Ok(3), Err(1), Ok(11)]);
+
+        let mut doubled = pairs(&[1, 2, 3, 4]).map_ok(|pair: &mut [u32]| {
+            pair[1] *= 2;
+            pair[1]
+        });
+        assert_eq!(doubled.next(), Some(Ok(4)));
+        assert_eq!(doubled.next(), Some(Ok(8)));
+        assert_eq!(doubled.next(), None);
+    }
+
+    #[test]
+    fn filter_ok() {
+        let large = || pairs(&[1, 2, 0, 4, 5, 6, 7, 1]).filter_ok(|pair: &&mut [u32]| pair[1] > 3);
+        let large = || large().map_ok(|pair: &mut [u32]| pair[0]);
+        check(large, &[Err(1), Ok(5)]);
+    }
+
+    #[test]
+    fn filter_map_ok() {
+        let firsts = || {
+            pairs(&[1, 2, 0, 4, 5, 6, 7, 1])
+                .filter_map_ok(|pair: &mut [u32]| (pair[1] > pair[0]).then_some(pair[0]))
+        };
+        check(firsts, &[Ok(1), Err(1), Ok(5)]);
+    }
+
+    #[test]
+    fn and_then_ok() {
+        let checked = || {
+            pairs(&[1, 2, 0, 4, 5, 6, 7, 1]).and_then_ok(|pair: &mut [u32]| match pair[1] {
+                1 => Err(100),
+                second => Ok(pair[0] * second),
+            })
+        };
+        check(checked, &[Ok(2), Err(1), Ok(30), Err(100)]);
+    }
+
+    #[test]
+    fn process_results_stops_at_first_error() {
+        let source = pairs(&[1, 2, 3, 4, 0, 0, 5, 6, 0, 1]);
+        let pulled = Rc::clone(&source.pulled);
+        let mut seen = Vec::new();
+        let result = source.process_results(|mut pairs| {
+            while let Some(pair) = pairs.next() {
+                seen.push(pair[0]);
+            }
+            assert!(pairs.next().is_none());
+            assert_eq!(pairs.size_hint(), (0, Some(0)));
+            seen.len()
+        });
+        assert_eq!(result, Err(2));
+        assert_eq!(seen, [1, 3]);
+        assert_eq!(pulled.get(), 3);
+
+        let source = pairs(&[1, 2, 0, 0, 5, 6]);
+        let pulled = Rc::clone(&source.pulled);
+        assert_eq!(source.process_results(|pairs| pairs.count()), Err(1));
+        assert_eq!(pulled.get(), 2);
+
+        let source = pairs(&[0, 2, 0, 0]);
+        let result = source.process_results(|mut pairs| {
+            pairs.try_fold(0, |n, _| Ok::<_, ()>(n + 1))
+        });
+        assert_eq!(result, Err(0));
+    }
+
+    #[test]
+    fn process_results_of_borrowed_items() {
+        let result = pairs(&[1, 2, 3, 4]).process_results(|pairs| {
+            pairs.fold(0, |sum, pair: &mut [u32]| {
+                pair[1] *= 10;
+                sum + pair[1]
+            })
+        });
+        assert_eq!(result, Ok(60));
+
+        let result = pairs(&[1, 2, 3, 4, 5, 6]).process_results(|mut pairs| {
+            let first = pairs.try_fold((), |(), pair| match pair[1] {
+                4 => Err(pair[0]),
+                _ => Ok(()),
+            });
+            (first, pairs.next().map(|pair| pair[0]))
+        });
+        assert_eq!(result, Ok((Err(3), Some(5))));
+    }
+
+    #[test]
+    fn map_windows() {
+        let mut sums = (1..=5).lending().map_windows::<3, _>(|w: &[i32; 3]| w.iter().sum::<i32>());
+        assert_eq!(sums.size_hint(), (3, Some(3)));
+        assert_eq!(sums.next(), Some(6));
+        assert_eq!(sums.next(), Some(9));
+        assert_eq!(sums.next(), Some(12));
+        assert_eq!(sums.next(), None);
+    }
+
+    #[test]
+    fn map_windows_of_projected_lent_items() {
+        let mut pairs = [1, 2, 3, 4, 5]
+            .into_iter()
+            .array_windows_lending::<2>()
+            .map(|w: &[u32; 2]| w[0] + w[1])
+            .map_windows::<2, _>(|w: &[u32; 2]| (w[0], w[1]));
+        assert_eq!(pairs.next(), Some((3, 5)));
+        assert_eq!(pairs.next(), Some((5, 7)));
+        assert_eq!(pairs.next(), Some((7, 9)));
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn map_windows_size_hint_of_unbounded_source() {
+        let windows = (0u64..).lending().map_windows::<2, _>(|w: &[u64; 2]| w[0]);
+        assert_eq!(windows.size_hint(), (usize::MAX - 1, None));
+    }
+
+    #[test]
+    fn map_windows_shorter_than_source() {
+        let mut windows = [1, 2].into_iter().lending().map_windows::<3, _>(|w: &[i32; 3]| w[0]);
+        assert_eq!(windows.size_hint(), (0, Some(0)));
+        assert_eq!(windows.next(), None);
+    }
 }
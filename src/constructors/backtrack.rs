@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Depth-first search over sequences of choices, lending every accepted partial solution.
+///
+/// Created by [`backtrack`]. The search keeps an explicit stack of pending choices instead
+/// of recursing, so it can be suspended after each solution.
+pub struct Backtrack<S, C, I: IntoIterator<Item = C>, F, A, P = fn(&S, &[C]) -> bool> {
+    state: S,
+    choices: F,
+    accept: A,
+    prune: P,
+    path: Vec<C>,
+    stack: Vec<I::IntoIter>,
+    visit: bool,
+}
+
+impl<S, C, I: IntoIterator<Item = C>, F, A, P> Backtrack<S, C, I, F, A, P> {
+    /// Skips every partial solution for which `prune` returns `true`, along with all of
+    /// its extensions.
+    pub fn prune<Q>(self, prune: Q) -> Backtrack<S, C, I, F, A, Q>
+    where
+        Q: FnMut(&S, &[C]) -> bool,
+    {
+        Backtrack {
+            state: self.state,
+            choices: self.choices,
+            accept: self.accept,
+            prune,
+            path: self.path,
+            stack: self.stack,
+            visit: self.visit,
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+}
+
+#[gat]
+impl<S, C, I, F, A, P> LendingIter for Backtrack<S, C, I, F, A, P>
+where
+    I: IntoIterator<Item = C>,
+    F: FnMut(&S, &[C]) -> I,
+    A: FnMut(&S, &[C]) -> bool,
+    P: FnMut(&S, &[C]) -> bool,
+{
+    type Item<'a> = &'a [C]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        loop {
+            if self.visit {
+                self.visit = false;
+                if (self.prune)(&self.state, &self.path) {
+                    self.path.pop();
+                    continue;
+                }
+                let choices = (self.choices)(&self.state, &self.path);
+                self.stack.push(choices.into_iter());
+                if (self.accept)(&self.state, &self.path) {
+                    return Some(&self.path);
+                }
+            }
+            match self.stack.last_mut()?.next() {
+                Some(choice) => {
+                    self.path.push(choice);
+                    self.visit = true;
+                }
+                None => {
+                    self.stack.pop();
+                    self.path.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Searches for every sequence of choices accepted by `accept`, in depth-first order.
+///
+/// Starting from the empty sequence, `choices` is called with `state` and the current
+/// partial solution to get the choices that may extend it. Every partial solution,
+/// including the empty one, for which `accept` returns `true` is lent; its extensions are
+/// still searched, so `choices` should return nothing for complete solutions.
+///
+/// ```ignore
+/// // Solutions to the n-queens problem, as the column of the queen in each row
+/// let queens = backtrack(
+///     8,
+///     |&n, rows: &[usize]| (0..n).filter(|&col| rows.iter().enumerate().all(|(row, &c)| {
+///         c != col && c.abs_diff(col) != rows.len() - row
+///     })).collect::<Vec<_>>(),
+///     |&n, rows| rows.len() == n,
+/// );
+/// ```
+pub fn backtrack<S, C, I, F, A>(state: S, choices: F, accept: A) -> Backtrack<S, C, I, F, A>
+where
+    I: IntoIterator<Item = C>,
+    F: FnMut(&S, &[C]) -> I,
+    A: FnMut(&S, &[C]) -> bool,
+{
+    Backtrack {
+        state,
+        choices,
+        accept,
+        prune: |_, _| false,
+        path: Vec::new(),
+        stack: Vec::new(),
+        visit: true,
+    }
+}
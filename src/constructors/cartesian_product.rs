@@ -0,0 +1,281 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Positions in each set of the elements of the next tuple to lend, the last one varying
+/// fastest.
+struct Odometer<X> {
+    indices: X,
+    /// First position whose element changed since the last lent tuple.
+    from: usize,
+    done: bool,
+}
+
+impl<X: AsRef<[usize]> + AsMut<[usize]>> Odometer<X> {
+    fn new<T, S: AsRef<[T]>>(indices: X, sets: &[S]) -> Self {
+        Odometer {
+            indices,
+            from: 0,
+            done: sets.iter().any(|set| set.as_ref().is_empty()),
+        }
+    }
+
+    /// Skips `n` tuples by adding `n` to the indices as a mixed radix number.
+    fn advance<T, S: AsRef<[T]>>(&mut self, sets: &[S], n: usize) {
+        let mut carry = n;
+        for (j, (index, set)) in self.indices.as_mut().iter_mut().zip(sets).enumerate().rev() {
+            if carry == 0 {
+                return;
+            }
+            let len = set.as_ref().len();
+            *index += carry % len;
+            carry /= len;
+            if *index >= len {
+                *index -= len;
+                carry += 1;
+            }
+            self.from = self.from.min(j);
+        }
+        if carry > 0 {
+            self.done = true;
+        }
+    }
+
+    /// Number of tuples left to lend, or `None` if it overflows.
+    fn remaining<T, S: AsRef<[T]>>(&self, sets: &[S]) -> Option<usize> {
+        if self.done {
+            return Some(0);
+        }
+        // The place value of a position only matters if its digit is not zero, so it may
+        // overflow as long as the digits above it are all zero
+        let (mut remaining, mut place) = (1usize, Some(1usize));
+        for (&index, set) in self.indices.as_ref().iter().zip(sets).rev() {
+            let len = set.as_ref().len();
+            let digit = len - 1 - index;
+            if digit > 0 {
+                remaining = remaining.checked_add(digit.checked_mul(place?)?)?;
+            }
+            place = place.and_then(|place| place.checked_mul(len));
+        }
+        Some(remaining)
+    }
+
+    fn size_hint<T, S: AsRef<[T]>>(&self, sets: &[S]) -> (usize, Option<usize>) {
+        match self.remaining(sets) {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Lends every tuple of the Cartesian product of some sets, reusing a single buffer of
+/// references.
+#[cfg(feature = "alloc")]
+pub struct CartesianProduct<'s, T, S> {
+    sets: &'s [S],
+    odometer: Odometer<Vec<usize>>,
+    buf: Vec<&'s T>,
+}
+
+#[cfg(feature = "alloc")]
+#[gat]
+impl<'s, T, S: AsRef<[T]>> LendingIter for CartesianProduct<'s, T, S> {
+    type Item<'a> = &'a [&'s T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.odometer.done {
+            return None;
+        }
+        let from = self.odometer.from;
+        self.buf.truncate(from);
+        self.buf.extend(
+            self.odometer.indices[from..]
+                .iter()
+                .zip(&self.sets[from..])
+                .map(|(&i, set)| &set.as_ref()[i]),
+        );
+        self.odometer.from = self.sets.len();
+        self.odometer.advance(self.sets, 1);
+        Some(&self.buf)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item<'_>> {
+        if !self.odometer.done {
+            self.odometer.advance(self.sets, n);
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.odometer.size_hint(self.sets)
+    }
+}
+
+/// Lends every tuple of the Cartesian product of `N` sets, without allocating.
+pub struct ArrayCartesianProduct<'s, T, const N: usize> {
+    sets: [&'s [T]; N],
+    odometer: Odometer<[usize; N]>,
+    buf: Option<[&'s T; N]>,
+}
+
+#[gat]
+impl<'s, T, const N: usize> LendingIter for ArrayCartesianProduct<'s, T, N> {
+    type Item<'a> = &'a [&'s T; N]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.odometer.done {
+            return None;
+        }
+        let (sets, indices) = (&self.sets, &self.odometer.indices);
+        let buf = self
+            .buf
+            .get_or_insert_with(|| core::array::from_fn(|j| &sets[j][indices[j]]));
+        for j in self.odometer.from..N {
+            buf[j] = &sets[j][indices[j]];
+        }
+        self.odometer.from = N;
+        self.odometer.advance(&self.sets, 1);
+        self.buf.as_ref()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item<'_>> {
+        if !self.odometer.done {
+            self.odometer.advance(&self.sets, n);
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.odometer.size_hint(&self.sets)
+    }
+}
+
+/// Lends every tuple of the Cartesian product of `sets` in lexicographic order, e.g.
+/// `[a, c]`, `[a, d]`, `[b, c]`, `[b, d]` for `[[a, b], [c, d]]`.
+///
+/// Works for any sets that can be viewed as slices, such as `&[&[T]]` or `&[Vec<T>]`.
+#[cfg(feature = "alloc")]
+pub fn cartesian_product<T, S: AsRef<[T]>>(sets: &[S]) -> CartesianProduct<'_, T, S> {
+    CartesianProduct {
+        sets,
+        odometer: Odometer::new(vec![0; sets.len()], sets),
+        buf: Vec::with_capacity(sets.len()),
+    }
+}
+
+/// Same as [`cartesian_product`] with the number of sets known at compile time, which does
+/// not require `alloc`.
+pub fn array_cartesian_product<T, const N: usize>(
+    sets: [&[T]; N],
+) -> ArrayCartesianProduct<'_, T, N> {
+    ArrayCartesianProduct {
+        odometer: Odometer::new([0; N], &sets),
+        sets,
+        buf: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::array_cartesian_product;
+    use crate::lending_iter::{LendedItem, LendingIter};
+
+    /// Every tuple of positions in sets of the given lengths, in lexicographic order.
+    fn brute_force(lens: &[usize]) -> Vec<Vec<usize>> {
+        lens.iter().fold(vec![vec![]], |tuples, &len| {
+            let tuples = tuples.iter();
+            tuples.flat_map(|tuple| (0..len).map(move |i| [&tuple[..], &[i]].concat())).collect()
+        })
+    }
+
+    /// Checks the lent tuples, and the size hint before every one of them, skipping `skip`
+    /// tuples with `nth` after the first `start` ones.
+    fn check<I, L>(mut iter: I, expected: &[Vec<usize>], start: usize, skip: usize, lent: L)
+    where
+        I: LendingIter,
+        L: for<'a> Fn(LendedItem<'a, I>) -> Vec<usize>,
+    {
+        let mut i = 0;
+        loop {
+            let left = expected.len().saturating_sub(i);
+            assert_eq!(iter.size_hint(), (left, Some(left)), "before tuple {i}");
+            let item = match i == start {
+                true => {
+                    i += skip;
+                    iter.nth(skip)
+                }
+                false => iter.next(),
+            };
+            let Some(item) = item else { break };
+            assert_eq!(lent(item), expected[i]);
+            i += 1;
+        }
+        assert!(i >= expected.len());
+        assert!(iter.next().is_none());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    fn sets(lens: &[usize]) -> Vec<Vec<usize>> {
+        lens.iter().map(|&len| (0..len).collect()).collect()
+    }
+
+    #[test]
+    fn array_product_against_brute_force() {
+        for lens in [[2, 3, 1], [1, 1, 1], [3, 0, 2], [0, 0, 0], [4, 1, 2]] {
+            let sets = sets(&lens);
+            let expected = brute_force(&lens);
+            for start in 0..=expected.len() {
+                for skip in 0..=expected.len() + 1 {
+                    let product = array_cartesian_product([&sets[0][..], &sets[1], &sets[2]]);
+                    let lent = |t: &[&usize; 3]| t.iter().map(|&&i| i).collect();
+                    check(product, &expected, start, skip, lent);
+                }
+            }
+        }
+        let lent = |t: &[&usize; 0]| t.iter().map(|&&i| i).collect();
+        check(array_cartesian_product::<usize, 0>([]), &[vec![]], 0, 0, lent);
+        check(array_cartesian_product::<usize, 0>([]), &[vec![]], 0, 1, lent);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn product_against_brute_force() {
+        use super::cartesian_product;
+
+        let cases: [&[usize]; 6] = [&[], &[3], &[0], &[2, 3, 2], &[2, 0, 3], &[1, 4, 1, 2]];
+        for lens in cases {
+            let sets = sets(lens);
+            let expected = brute_force(lens);
+            for start in 0..=expected.len() {
+                for skip in 0..=expected.len() + 1 {
+                    let lent = |t: &[&usize]| t.iter().map(|&&i| i).collect();
+                    check(cartesian_product(&sets), &expected, start, skip, lent);
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "alloc", target_pointer_width = "64"))]
+    #[test]
+    fn size_hint_of_overflowing_product() {
+        use super::cartesian_product;
+
+        let sets = vec![[0, 1]; 65];
+        let mut product = cartesian_product(&sets);
+        assert_eq!(product.size_hint(), (usize::MAX, None));
+        assert!(product.nth(usize::MAX).is_some());
+        assert_eq!(product.size_hint(), (usize::MAX, None));
+        // 2^65 tuples, 2^64 lent and 2^64 - 10 more skipped or lent
+        assert!(product.nth(usize::MAX - 10).is_some());
+        assert_eq!(product.size_hint(), (10, Some(10)));
+        assert_eq!(product.count(), 10);
+    }
+}
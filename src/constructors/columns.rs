@@ -0,0 +1,251 @@
+use core::{
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Mutable view of evenly spaced elements of a slice, such as a column of a row-major
+/// matrix.
+pub struct StrideMut<'a, T> {
+    data: &'a mut [T],
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T> StrideMut<'a, T> {
+    /// View of the `len` elements of `buf` every `stride` elements starting from `start`.
+    fn new(buf: &'a mut [T], start: usize, stride: usize, len: usize) -> Self {
+        let data = match len {
+            // `start` may be past the end of `buf` when there is no whole row
+            0 => &mut buf[..0],
+            _ => &mut buf[start..start + (len - 1) * stride + 1],
+        };
+        StrideMut {
+            data,
+            stride,
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.data.get(i.checked_mul(self.stride)?)
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.data.get_mut(i.checked_mul(self.stride)?)
+    }
+
+    pub fn iter(&self) -> StepBy<slice::Iter<'_, T>> {
+        self.data.iter().step_by(self.stride)
+    }
+
+    pub fn iter_mut(&mut self) -> StepBy<slice::IterMut<'_, T>> {
+        self.data.iter_mut().step_by(self.stride)
+    }
+}
+
+impl<T> Index<usize> for StrideMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.len;
+        self.get(i).unwrap_or_else(|| panic!("index {i} out of range for length {len}"))
+    }
+}
+
+impl<T> IndexMut<usize> for StrideMut<'_, T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.len;
+        self.get_mut(i).unwrap_or_else(|| panic!("index {i} out of range for length {len}"))
+    }
+}
+
+impl<'a, T> IntoIterator for StrideMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = StepBy<slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter_mut().step_by(self.stride)
+    }
+}
+
+impl<'b, T> IntoIterator for &'b mut StrideMut<'_, T> {
+    type Item = &'b mut T;
+    type IntoIter = StepBy<slice::IterMut<'b, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Lends a mutable view of every column of a row-major matrix, from left to right.
+///
+/// Created by [`columns_mut`].
+pub struct ColumnsMut<'s, T> {
+    buf: &'s mut [T],
+    width: usize,
+    x: usize,
+}
+
+#[gat]
+impl<'s, T> LendingIter for ColumnsMut<'s, T> {
+    type Item<'a> = StrideMut<'a, T>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.x >= self.width {
+            return None;
+        }
+        self.x += 1;
+        let height = self.buf.len() / self.width;
+        Some(StrideMut::new(self.buf, self.x - 1, self.width, height))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.width - self.x;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Lends a mutable view of every diagonal going down and right of a row-major matrix,
+/// starting from the bottom left corner and ending at the top right one.
+///
+/// Created by [`diagonals_mut`].
+pub struct DiagonalsMut<'s, T> {
+    buf: &'s mut [T],
+    width: usize,
+    d: usize,
+}
+
+impl<T> DiagonalsMut<'_, T> {
+    /// Total number of diagonals.
+    fn total(&self) -> usize {
+        match self.buf.len() / self.width {
+            0 => 0,
+            height => height + self.width - 1,
+        }
+    }
+}
+
+#[gat]
+impl<'s, T> LendingIter for DiagonalsMut<'s, T> {
+    type Item<'a> = StrideMut<'a, T>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let height = self.buf.len() / self.width;
+        if self.d >= self.total() {
+            return None;
+        }
+        let (y, x) = match self.d < height {
+            true => (height - 1 - self.d, 0),
+            false => (0, self.d + 1 - height),
+        };
+        self.d += 1;
+        let len = (height - y).min(self.width - x);
+        Some(StrideMut::new(self.buf, y * self.width + x, self.width + 1, len))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total() - self.d;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Lends every column of a matrix stored row by row in `buf`, `width` elements per row.
+/// Trailing elements not making up a whole row are ignored.
+///
+/// # Panics
+/// Panics if `width` is zero.
+pub fn columns_mut<T>(buf: &mut [T], width: usize) -> ColumnsMut<'_, T> {
+    assert!(width > 0, "matrix width must be non-zero");
+    ColumnsMut { buf, width, x: 0 }
+}
+
+/// Lends every diagonal of a matrix stored row by row in `buf`, `width` elements per row.
+/// Trailing elements not making up a whole row are ignored.
+///
+/// # Panics
+/// Panics if `width` is zero.
+pub fn diagonals_mut<T>(buf: &mut [T], width: usize) -> DiagonalsMut<'_, T> {
+    assert!(width > 0, "matrix width must be non-zero");
+    DiagonalsMut { buf, width, d: 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{columns_mut, diagonals_mut};
+    use crate::prelude::LendingIter;
+
+    #[test]
+    fn columns() {
+        let mut buf = [1, 2, 3, 4, 5, 6, 7];
+        let mut columns = columns_mut(&mut buf, 3);
+        let mut seen = Vec::new();
+        while let Some(mut column) = columns.next() {
+            column[1] *= 10;
+            seen.push(column.iter().copied().collect::<Vec<_>>());
+        }
+        assert_eq!(seen, [[1, 40], [2, 50], [3, 60]]);
+        assert_eq!(buf, [1, 2, 3, 40, 50, 60, 7]);
+    }
+
+    #[test]
+    fn columns_without_whole_row() {
+        let mut buf = [1, 2];
+        let mut columns = columns_mut(&mut buf, 5);
+        assert_eq!(columns.size_hint(), (5, Some(5)));
+        let mut count = 0;
+        while let Some(column) = columns.next() {
+            assert!(column.is_empty());
+            assert!(column.get(0).is_none());
+            count += 1;
+        }
+        assert_eq!(count, 5);
+    }
+
+    #[test]
+    fn diagonals() {
+        let mut buf = [1, 2, 3, 4, 5, 6];
+        let mut diagonals = diagonals_mut(&mut buf, 3);
+        let mut seen = Vec::new();
+        while let Some(diagonal) = diagonals.next() {
+            seen.push(diagonal.into_iter().map(|x| *x).collect::<Vec<_>>());
+        }
+        assert_eq!(seen, [vec![4], vec![1, 5], vec![2, 6], vec![3]]);
+        assert!(diagonals_mut(&mut [1, 2], 3).next().is_none());
+    }
+
+    #[test]
+    #[should_panic = "index 3 out of range for length 3"]
+    fn index_out_of_range() {
+        let mut buf = [0; 6];
+        let mut columns = columns_mut(&mut buf, 2);
+        let column = columns.next().unwrap();
+        let _ = column[3];
+    }
+
+    #[test]
+    #[should_panic = "out of range"]
+    fn index_overflowing_stride() {
+        let mut buf = [0; 6];
+        let mut columns = columns_mut(&mut buf, 2);
+        let mut column = columns.next().unwrap();
+        column[usize::MAX / 2 + 1] = 1;
+    }
+}
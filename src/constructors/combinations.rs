@@ -0,0 +1,354 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Positions in the slice of the elements of the current combination, in increasing order
+/// (non-decreasing when elements may be repeated).
+pub(super) struct Indices<X> {
+    pub(super) indices: X,
+    n: usize,
+    replacement: bool,
+    first: bool,
+    done: bool,
+}
+
+impl<X: AsRef<[usize]> + AsMut<[usize]>> Indices<X> {
+    pub(super) fn new(mut indices: X, n: usize, replacement: bool) -> Self {
+        for (j, index) in indices.as_mut().iter_mut().enumerate() {
+            *index = if replacement { 0 } else { j };
+        }
+        let k = indices.as_ref().len();
+        let done = k > 0 && if replacement { n == 0 } else { k > n };
+        Indices {
+            indices,
+            n,
+            replacement,
+            first: true,
+            done,
+        }
+    }
+
+    /// Moves to the next combination in lexicographic order, returning the first position
+    /// whose index changed, or `None` once exhausted.
+    pub(super) fn advance(&mut self) -> Option<usize> {
+        if self.done {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+            return Some(0);
+        }
+        let (n, replacement) = (self.n, self.replacement);
+        let indices = self.indices.as_mut();
+        let k = indices.len();
+        let max = |j: usize| if replacement { n - 1 } else { n - k + j };
+        let Some(i) = (0..k).rev().find(|&j| indices[j] < max(j)) else {
+            self.done = true;
+            return None;
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = if replacement { indices[i] } else { indices[j - 1] + 1 };
+        }
+        Some(i)
+    }
+
+    /// Number of combinations left to lend, or `None` if it overflows.
+    pub(super) fn remaining(&self) -> Option<usize> {
+        if self.done {
+            return Some(0);
+        }
+        let indices = self.indices.as_ref();
+        let k = indices.len();
+        // Shifting the j-th index by j maps combinations with replacement onto those without
+        // replacement out of n + k - 1 elements, preserving their order.
+        let (n, shift) = match self.replacement {
+            true => ((self.n + k).saturating_sub(1), 1),
+            false => (self.n, 0),
+        };
+        // Mirroring every index reverses the lexicographic order, and the rank of the
+        // mirrored combination in colexicographic order is given by the combinatorial
+        // number system.
+        let after = indices.iter().enumerate().try_fold(0usize, |acc, (j, &c)| {
+            acc.checked_add(binomial(n - 1 - (c + j * shift), k - j)?)
+        })?;
+        match self.first {
+            true => after.checked_add(1),
+            false => Some(after),
+        }
+    }
+}
+
+pub(super) fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    // Every partial product is a binomial coefficient no larger than the result, so only
+    // the multiplication before the division needs to be widened
+    (0..k).try_fold(1usize, |acc, j| {
+        usize::try_from(acc as u128 * (n - j) as u128 / (j + 1) as u128).ok()
+    })
+}
+
+fn size_hint<X: AsRef<[usize]> + AsMut<[usize]>>(indices: &Indices<X>) -> (usize, Option<usize>) {
+    match indices.remaining() {
+        Some(remaining) => (remaining, Some(remaining)),
+        None => (usize::MAX, None),
+    }
+}
+
+/// Lends every `k`-combination of the elements of a slice in lexicographic order of their
+/// positions, reusing a single buffer of references.
+#[cfg(feature = "alloc")]
+pub struct Combinations<'s, T> {
+    slice: &'s [T],
+    indices: Indices<Vec<usize>>,
+    buf: Vec<&'s T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'s, T: Clone> Combinations<'s, T> {
+    /// Lends the combinations as slices of clones instead. Only the elements that changed
+    /// since the last combination are cloned.
+    pub fn cloned(self) -> CombinationsCloned<'s, T> {
+        CombinationsCloned {
+            slice: self.slice,
+            indices: self.indices,
+            buf: self.buf.into_iter().cloned().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[gat]
+impl<'s, T> LendingIter for Combinations<'s, T> {
+    type Item<'a> = &'a [&'s T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let from = self.indices.advance()?;
+        self.buf.truncate(from);
+        self.buf
+            .extend(self.indices.indices[from..].iter().map(|&i| &self.slice[i]));
+        Some(&self.buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(&self.indices)
+    }
+}
+
+/// Lends every `k`-combination of the elements of a slice, cloned into a reused buffer.
+///
+/// Created by [`Combinations::cloned`].
+#[cfg(feature = "alloc")]
+pub struct CombinationsCloned<'s, T> {
+    slice: &'s [T],
+    indices: Indices<Vec<usize>>,
+    buf: Vec<T>,
+}
+
+#[cfg(feature = "alloc")]
+#[gat]
+impl<'s, T: Clone> LendingIter for CombinationsCloned<'s, T> {
+    type Item<'a> = &'a [T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let from = self.indices.advance()?;
+        self.buf.truncate(from);
+        self.buf
+            .extend(self.indices.indices[from..].iter().map(|&i| self.slice[i].clone()));
+        Some(&self.buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(&self.indices)
+    }
+}
+
+/// Lends every `K`-combination of the elements of a slice in lexicographic order of their
+/// positions, without allocating.
+pub struct ArrayCombinations<'s, T, const K: usize> {
+    slice: &'s [T],
+    indices: Indices<[usize; K]>,
+    buf: Option<[&'s T; K]>,
+}
+
+#[gat]
+impl<'s, T, const K: usize> LendingIter for ArrayCombinations<'s, T, K> {
+    type Item<'a> = &'a [&'s T; K]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let from = self.indices.advance()?;
+        let (slice, indices) = (self.slice, &self.indices.indices);
+        let buf = self.buf.get_or_insert_with(|| indices.map(|i| &slice[i]));
+        for (elem, &i) in buf.iter_mut().zip(indices).skip(from) {
+            *elem = &slice[i];
+        }
+        Some(buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(&self.indices)
+    }
+}
+
+/// Lends every `k`-combination of the elements of `slice`, e.g. `[a, b]`, `[a, c]`,
+/// `[b, c]` for `k = 2` and `[a, b, c]`.
+#[cfg(feature = "alloc")]
+pub fn combinations<T>(slice: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        slice,
+        indices: Indices::new(vec![0; k], slice.len(), false),
+        buf: Vec::with_capacity(k),
+    }
+}
+
+/// Lends every `k`-combination of the elements of `slice` where elements may be repeated,
+/// e.g. `[a, a]`, `[a, b]`, `[b, b]` for `k = 2` and `[a, b]`.
+#[cfg(feature = "alloc")]
+pub fn combinations_with_replacement<T>(slice: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        slice,
+        indices: Indices::new(vec![0; k], slice.len(), true),
+        buf: Vec::with_capacity(k),
+    }
+}
+
+/// Same as [`combinations`] with `k` known at compile time, which does not require `alloc`.
+pub fn array_combinations<T, const K: usize>(slice: &[T]) -> ArrayCombinations<'_, T, K> {
+    ArrayCombinations {
+        slice,
+        indices: Indices::new([0; K], slice.len(), false),
+        buf: None,
+    }
+}
+
+/// Same as [`combinations_with_replacement`] with `K` known at compile time, which does
+/// not require `alloc`.
+pub fn array_combinations_with_replacement<T, const K: usize>(
+    slice: &[T],
+) -> ArrayCombinations<'_, T, K> {
+    ArrayCombinations {
+        slice,
+        indices: Indices::new([0; K], slice.len(), true),
+        buf: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{array_combinations, array_combinations_with_replacement, binomial};
+    use crate::lending_iter::{LendedItem, LendingIter};
+
+    /// Every `k`-tuple of positions below `n`, increasing or non-decreasing, in
+    /// lexicographic order.
+    fn brute_force(n: usize, k: usize, replacement: bool) -> Vec<Vec<usize>> {
+        let mut tuples = vec![vec![]];
+        for _ in 0..k {
+            tuples = tuples
+                .into_iter()
+                .flat_map(|tuple: Vec<usize>| {
+                    let from = match tuple.last() {
+                        Some(&last) if replacement => last,
+                        Some(&last) => last + 1,
+                        None => 0,
+                    };
+                    (from..n).map(move |i| [tuple.clone(), vec![i]].concat())
+                })
+                .collect();
+        }
+        tuples
+    }
+
+    /// Checks the lent combinations, and the size hint before every one of them.
+    fn check<I, L>(mut iter: I, expected: &[Vec<usize>], lent: L)
+    where
+        I: LendingIter,
+        L: for<'a> Fn(LendedItem<'a, I>) -> Vec<usize>,
+    {
+        let mut seen = Vec::new();
+        loop {
+            let left = expected.len() - seen.len();
+            assert_eq!(iter.size_hint(), (left, Some(left)), "after {seen:?}");
+            let Some(item) = iter.next() else { break };
+            seen.push(lent(item));
+        }
+        assert_eq!(seen, expected);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn binomials() {
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(5, 0), Some(1));
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 5), Some(1));
+        assert_eq!(binomial(3, 5), Some(0));
+        assert_eq!(binomial(usize::MAX, 1), Some(usize::MAX));
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert_eq!(binomial(66, 33), Some(7219428434016265740));
+            assert_eq!(binomial(68, 34), None);
+        }
+    }
+
+    #[test]
+    fn array_combinations_against_brute_force() {
+        let positions: Vec<usize> = (0..5).collect();
+        for n in 0..=5 {
+            let slice = &positions[..n];
+            let lent = |c: &[&usize; 0]| c.iter().map(|&&i| i).collect();
+            check(array_combinations::<_, 0>(slice), &brute_force(n, 0, false), lent);
+            let lent = |c: &[&usize; 2]| c.iter().map(|&&i| i).collect();
+            check(array_combinations::<_, 2>(slice), &brute_force(n, 2, false), lent);
+            let lent = |c: &[&usize; 3]| c.iter().map(|&&i| i).collect();
+            check(array_combinations::<_, 3>(slice), &brute_force(n, 3, false), lent);
+            let with_replacement = array_combinations_with_replacement::<_, 3>(slice);
+            check(with_replacement, &brute_force(n, 3, true), lent);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn combinations_against_brute_force() {
+        use super::{combinations, combinations_with_replacement};
+
+        let positions: Vec<usize> = (0..6).collect();
+        for n in 0..=6 {
+            let slice = &positions[..n];
+            for k in 0..=7 {
+                let lent = |c: &[&usize]| c.iter().map(|&&i| i).collect();
+                check(combinations(slice, k), &brute_force(n, k, false), lent);
+                let with_replacement = combinations_with_replacement(slice, k);
+                check(with_replacement, &brute_force(n, k, true), lent);
+                let lent = |c: &[usize]| c.to_vec();
+                check(combinations(slice, k).cloned(), &brute_force(n, k, false), lent);
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn size_hint_of_many_combinations() {
+        use super::combinations;
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let count = 7219428434016265740;
+            assert_eq!(combinations(&[(); 66], 33).size_hint(), (count, Some(count)));
+        }
+        assert_eq!(combinations(&[(); 200], 100).size_hint(), (usize::MAX, None));
+    }
+}
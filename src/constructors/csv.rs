@@ -0,0 +1,387 @@
+use core::fmt;
+use std::{
+    error::Error,
+    io::{self, BufRead, ErrorKind},
+    vec::Vec,
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Dialect of the CSV input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    delimiter: u8,
+    quote: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            delimiter: b',',
+            quote: b'"',
+        }
+    }
+}
+
+impl Config {
+    /// Byte separating the fields of a record, `,` by default.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Byte enclosing quoted fields, `"` by default. A quoted field may contain delimiters
+    /// and line breaks, and the quote itself when doubled.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+}
+
+/// Position in the input, counting lines from 1 and bytes from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: u64,
+    pub byte: u64,
+}
+
+/// Record of a CSV input, borrowed from the reader.
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    data: &'a [u8],
+    ends: &'a [usize],
+    position: Position,
+}
+
+impl<'a> Record<'a> {
+    /// Number of fields of the record.
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Unescaped contents of the `i`-th field.
+    pub fn get(&self, i: usize) -> Option<&'a [u8]> {
+        let end = *self.ends.get(i)?;
+        let start = match i {
+            0 => 0,
+            _ => self.ends[i - 1],
+        };
+        Some(&self.data[start..end])
+    }
+
+    /// Unescaped contents of every field.
+    pub fn fields(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let data = self.data;
+        let starts = core::iter::once(0).chain(self.ends.iter().copied());
+        starts.zip(self.ends).map(move |(start, &end)| &data[start..end])
+    }
+
+    /// Position of the first byte of the record.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvErrorKind {
+    Io(io::Error),
+    /// A quote inside an unquoted field.
+    UnexpectedQuote,
+    /// Something other than a delimiter or a line break after the closing quote of a field.
+    UnexpectedAfterQuote,
+    /// The input ended inside a quoted field.
+    UnterminatedQuote,
+}
+
+/// Error lent by [`CsvRecords`], along with the position where it occurred.
+#[derive(Debug)]
+pub struct CsvError {
+    pub kind: CsvErrorKind,
+    pub position: Position,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CsvErrorKind::Io(e) => e.fmt(f)?,
+            CsvErrorKind::UnexpectedQuote => f.write_str("quote in unquoted field")?,
+            CsvErrorKind::UnexpectedAfterQuote => f.write_str("unexpected byte after quote")?,
+            CsvErrorKind::UnterminatedQuote => f.write_str("unterminated quoted field")?,
+        }
+        write!(f, " at line {}, byte {}", self.position.line, self.position.byte)
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            CsvErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    StartField,
+    Unquoted,
+    Quoted,
+    /// A quote was found in a quoted field, which is either escaped or closing it.
+    QuoteInQuoted,
+    /// Skipping the rest of the line after an error.
+    Skip,
+}
+
+/// Parsing state, kept apart from the reader so that it can be updated while the reader's
+/// buffer is borrowed.
+struct Parser {
+    config: Config,
+    data: Vec<u8>,
+    ends: Vec<usize>,
+    state: State,
+    in_record: bool,
+    /// Whether the last byte was a carriage return, so that a line feed right after it ends
+    /// the same line.
+    after_cr: bool,
+    start: Position,
+    position: Position,
+}
+
+impl Parser {
+    /// Parses one byte, returning `Some` once it ends a record or is invalid.
+    fn feed(&mut self, byte: u8) -> Option<Result<(), CsvErrorKind>> {
+        let result = self.step(byte);
+        self.position.byte += 1;
+        if byte == b'\r' || (byte == b'\n' && !self.after_cr) {
+            self.position.line += 1;
+        }
+        self.after_cr = byte == b'\r';
+        result
+    }
+
+    fn step(&mut self, byte: u8) -> Option<Result<(), CsvErrorKind>> {
+        let Config { delimiter, quote } = self.config;
+        if !self.in_record && self.state != State::Skip {
+            // Blank lines, including the line feed of a CRLF, are ignored
+            if let b'\n' | b'\r' = byte {
+                return None;
+            }
+            self.in_record = true;
+            self.start = self.position;
+        }
+        match (self.state, byte) {
+            (State::Skip, b'\n' | b'\r') => self.state = State::StartField,
+            (State::Skip, _) => {}
+            (State::StartField, b) if b == quote => self.state = State::Quoted,
+            (State::StartField | State::Unquoted | State::QuoteInQuoted, b) if b == delimiter => {
+                self.ends.push(self.data.len());
+                self.state = State::StartField;
+            }
+            (State::StartField | State::Unquoted | State::QuoteInQuoted, b'\n' | b'\r') => {
+                self.end_record();
+                return Some(Ok(()));
+            }
+            (State::Unquoted, b) if b == quote => return Some(Err(CsvErrorKind::UnexpectedQuote)),
+            (State::StartField | State::Unquoted, b) => {
+                self.data.push(b);
+                self.state = State::Unquoted;
+            }
+            (State::Quoted, b) if b == quote => self.state = State::QuoteInQuoted,
+            (State::Quoted, b) => self.data.push(b),
+            (State::QuoteInQuoted, b) if b == quote => {
+                self.data.push(b);
+                self.state = State::Quoted;
+            }
+            (State::QuoteInQuoted, _) => return Some(Err(CsvErrorKind::UnexpectedAfterQuote)),
+        }
+        None
+    }
+
+    fn end_record(&mut self) {
+        self.ends.push(self.data.len());
+        self.in_record = false;
+        self.state = State::StartField;
+    }
+
+    fn record(&self) -> Record<'_> {
+        Record {
+            data: &self.data,
+            ends: &self.ends,
+            position: self.start,
+        }
+    }
+
+    fn error(&self, kind: CsvErrorKind) -> CsvError {
+        CsvError {
+            kind,
+            position: self.position,
+        }
+    }
+}
+
+/// Lends every record of a CSV input, reusing a single buffer for their contents.
+///
+/// Created by [`csv_records`].
+pub struct CsvRecords<R> {
+    reader: R,
+    parser: Parser,
+    done: bool,
+}
+
+impl<R> CsvRecords<R> {
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[gat]
+impl<R: BufRead> LendingIter for CsvRecords<R> {
+    type Item<'a> = Result<Record<'a>, CsvError>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done {
+            return None;
+        }
+        let parser = &mut self.parser;
+        if !parser.in_record {
+            parser.data.clear();
+            parser.ends.clear();
+        }
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(parser.error(CsvErrorKind::Io(e)))),
+            };
+            if buf.is_empty() {
+                self.done = true;
+                return match parser.state {
+                    State::Quoted => Some(Err(parser.error(CsvErrorKind::UnterminatedQuote))),
+                    _ if parser.in_record => {
+                        parser.end_record();
+                        Some(Ok(parser.record()))
+                    }
+                    _ => None,
+                };
+            }
+            let mut outcome = None;
+            let mut used = 0;
+            for &byte in buf {
+                let position = parser.position;
+                used += 1;
+                if let Some(result) = parser.feed(byte) {
+                    outcome = Some(result.map_err(|kind| CsvError { kind, position }));
+                    break;
+                }
+            }
+            self.reader.consume(used);
+            match outcome {
+                Some(Ok(())) => return Some(Ok(parser.record())),
+                Some(Err(e)) => {
+                    parser.state = State::Skip;
+                    parser.in_record = false;
+                    return Some(Err(e));
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Lends every record of the CSV input read from `reader`.
+///
+/// Records end with a line feed, a carriage return, or both, and blank lines are skipped.
+/// Fields are lent unescaped, with their enclosing quotes removed and doubled quotes
+/// replaced with single ones.
+///
+/// A malformed record is lent as an error, after which parsing resumes on the next line.
+pub fn csv_records<R: BufRead>(reader: R, config: Config) -> CsvRecords<R> {
+    let start = Position { line: 1, byte: 0 };
+    CsvRecords {
+        reader,
+        parser: Parser {
+            config,
+            data: Vec::new(),
+            ends: Vec::new(),
+            state: State::StartField,
+            in_record: false,
+            after_cr: false,
+            start,
+            position: start,
+        },
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::BufReader, vec::Vec};
+
+    use super::{csv_records, Config, CsvErrorKind, Position};
+    use crate::prelude::LendingIter;
+
+    /// Fields of a record, or the kind and line of an error.
+    type Parsed = Result<Vec<Vec<u8>>, (CsvErrorKind, u64)>;
+
+    fn parse(input: &[u8], config: Config) -> Vec<Parsed> {
+        let mut records = csv_records(BufReader::with_capacity(3, input), config);
+        let mut parsed = Vec::new();
+        while let Some(record) = records.next() {
+            parsed.push(match record {
+                Ok(record) => Ok(record.fields().map(<[u8]>::to_vec).collect()),
+                Err(e) => Err((e.kind, e.position.line)),
+            });
+        }
+        parsed
+    }
+
+    fn fields(fields: &[&str]) -> Vec<Vec<u8>> {
+        fields.iter().map(|field| field.as_bytes().to_vec()).collect()
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let parsed = parse(b"a,\"b,\"\"c\"\"\"\n\"d\ne\",f", Config::default());
+        assert!(matches!(&parsed[..], [Ok(first), Ok(second)]
+            if *first == fields(&["a", "b,\"c\""]) && *second == fields(&["d\ne", "f"])));
+    }
+
+    #[test]
+    fn line_terminators() {
+        for terminator in ["\n", "\r", "\r\n"] {
+            let input = ["a;b", "c\"x;d", "", "e;f", ""].join(terminator);
+            let parsed = parse(input.as_bytes(), Config::default().delimiter(b';'));
+            assert!(
+                matches!(&parsed[..], [Ok(first), Err((CsvErrorKind::UnexpectedQuote, 2)), Ok(last)]
+                    if *first == fields(&["a", "b"]) && *last == fields(&["e", "f"])),
+                "{terminator:?}: {parsed:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn record_positions() {
+        for terminator in ["\n", "\r", "\r\n"] {
+            let input = ["a", "\"b", "c\"", "d"].join(terminator);
+            let mut records = csv_records(input.as_bytes(), Config::default());
+            let mut lines = Vec::new();
+            while let Some(record) = records.next() {
+                lines.push(record.unwrap().position().line);
+            }
+            assert_eq!(lines, [1, 2, 4], "{terminator:?}");
+        }
+        let mut records = csv_records(&b"a\r\nb"[..], Config::default());
+        records.next();
+        let position = records.next().unwrap().unwrap().position();
+        assert_eq!(position, Position { line: 2, byte: 3 });
+    }
+}
@@ -0,0 +1,274 @@
+use alloc::vec::Vec;
+use core::{mem, ptr};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Layout of the vector while a cursor walks it: elements before `write` have been visited
+/// and kept, elements from `read` to `len` are yet to be visited, and the gap in between is
+/// uninitialized.
+///
+/// The length of the vector itself is set to zero meanwhile, so that leaking the cursor
+/// leaks the elements instead of exposing the gap.
+struct Gap {
+    write: usize,
+    read: usize,
+    len: usize,
+}
+
+/// Lends a [`Slot`] for every element of a vector, through which it can be removed or
+/// replaced and elements inserted after it.
+///
+/// Created by [`cursor_mut`]. The vector is compacted as the cursor moves, and is left
+/// with its final contents once the cursor is dropped.
+pub struct CursorMut<'v, T> {
+    vec: &'v mut Vec<T>,
+    gap: Gap,
+}
+
+impl<T> Drop for CursorMut<'_, T> {
+    fn drop(&mut self) {
+        let Gap { write, read, len } = self.gap;
+        let base = self.vec.as_mut_ptr();
+        // SAFETY:
+        // Elements from `read` to `len` are initialized, and moving them right after the
+        // kept elements leaves every element up to the new length initialized
+        unsafe {
+            ptr::copy(base.add(read), base.add(write), len - read);
+            self.vec.set_len(write + (len - read));
+        }
+    }
+}
+
+#[gat]
+impl<'v, T> LendingIter for CursorMut<'v, T> {
+    type Item<'a> = Slot<'a, T>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let gap = &mut self.gap;
+        if gap.read == gap.len {
+            return None;
+        }
+        if gap.write != gap.read {
+            let base = self.vec.as_mut_ptr();
+            // SAFETY:
+            // The element at `read` is initialized and moved into the gap, which it leaves
+            // on the other side
+            unsafe { ptr::copy_nonoverlapping(base.add(gap.read), base.add(gap.write), 1) };
+        }
+        gap.read += 1;
+        gap.write += 1;
+        Some(Slot {
+            index: gap.write - 1,
+            vec: self.vec,
+            gap,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.gap.len - self.gap.read;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Element of a vector lent by [`CursorMut`].
+pub struct Slot<'a, T> {
+    vec: &'a mut Vec<T>,
+    gap: &'a mut Gap,
+    index: usize,
+}
+
+impl<T> Slot<'_, T> {
+    pub fn get(&self) -> &T {
+        // SAFETY:
+        // The element at `index` is kept and initialized until the slot is removed
+        unsafe { &*self.vec.as_ptr().add(self.index) }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        // SAFETY:
+        // The element at `index` is kept and initialized until the slot is removed
+        unsafe { &mut *self.vec.as_mut_ptr().add(self.index) }
+    }
+
+    /// Replaces the element with `value`, returning the previous one.
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the element from the vector and returns it.
+    ///
+    /// This takes constant time, unless elements have been inserted after it, which are
+    /// then shifted.
+    pub fn remove(self) -> T {
+        let base = self.vec.as_mut_ptr();
+        // SAFETY:
+        // The element is moved out and overwritten by the elements inserted after it, if
+        // any, so the kept elements stay contiguous
+        unsafe {
+            let value = ptr::read(base.add(self.index));
+            let inserted = self.gap.write - self.index - 1;
+            ptr::copy(base.add(self.index + 1), base.add(self.index), inserted);
+            self.gap.write -= 1;
+            value
+        }
+    }
+
+    /// Inserts `value` after the element and the ones previously inserted after it. The
+    /// cursor does not visit inserted elements.
+    ///
+    /// This takes amortized constant time, as the gap left by removed elements is reused,
+    /// and reopened with the spare capacity of the vector when there is none.
+    pub fn insert_after(&mut self, value: T) {
+        if self.gap.write == self.gap.read {
+            self.open_gap();
+        }
+        // SAFETY:
+        // The gap is non-empty, so `write` is within capacity and uninitialized
+        unsafe { ptr::write(self.vec.as_mut_ptr().add(self.gap.write), value) };
+        self.gap.write += 1;
+    }
+
+    fn open_gap(&mut self) {
+        let gap = &mut *self.gap;
+        let tail = gap.len - gap.read;
+        let spare = self.vec.capacity() - gap.len;
+        if spare > 0 {
+            let base = self.vec.as_mut_ptr();
+            // SAFETY:
+            // The elements yet to be visited are moved to the end of the capacity, which fits
+            // them as `spare` is the capacity past `len`
+            unsafe { ptr::copy(base.add(gap.read), base.add(gap.read + spare), tail) };
+            gap.read += spare;
+            gap.len += spare;
+            return;
+        }
+        // The vector cannot reallocate by itself, as its length is zero and it would only
+        // keep that many elements, so the elements are moved into a new buffer by hand
+        let capacity = gap.len.checked_add(1).expect("capacity overflow").max(2 * gap.len);
+        let mut vec = Vec::with_capacity(capacity);
+        let extra = vec.capacity() - gap.len;
+        let (old, new) = (self.vec.as_ptr(), vec.as_mut_ptr());
+        // SAFETY:
+        // The kept elements and the ones yet to be visited are initialized, and are moved to
+        // the start and the end of the new buffer, which fits them with room to spare
+        unsafe {
+            ptr::copy_nonoverlapping(old, new, gap.write);
+            ptr::copy_nonoverlapping(old.add(gap.read), new.add(gap.read + extra), tail);
+        }
+        // The old buffer has a length of zero, so it is freed without dropping the elements
+        mem::swap(self.vec, &mut vec);
+        gap.read += extra;
+        gap.len += extra;
+    }
+}
+
+/// Lends a [`Slot`] for every element of `vec` in order, to remove, replace or insert
+/// elements while walking it.
+///
+/// Removed elements are compacted away as the cursor moves, like [`Vec::retain_mut`], so
+/// that walking the whole vector takes linear time.
+pub fn cursor_mut<T>(vec: &mut Vec<T>) -> CursorMut<'_, T> {
+    let len = vec.len();
+    // SAFETY:
+    // The elements are tracked by the cursor, which restores the length when dropped
+    unsafe { vec.set_len(0) };
+    CursorMut {
+        vec,
+        gap: Gap {
+            write: 0,
+            read: 0,
+            len,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{rc::Rc, string::String, vec, vec::Vec};
+
+    use super::cursor_mut;
+    use crate::prelude::LendingIter;
+
+    #[test]
+    fn inserts_reallocate() {
+        let mut vec: Vec<String> = (0..4).map(|i| i.to_string()).collect();
+        vec.shrink_to_fit();
+        let mut cursor = cursor_mut(&mut vec);
+        while let Some(mut slot) = cursor.next() {
+            let value = slot.get().clone();
+            for i in 0..3 {
+                slot.insert_after(format!("{value}.{i}"));
+            }
+        }
+        drop(cursor);
+        let expected: Vec<String> = (0..4)
+            .flat_map(|i| [i.to_string(), format!("{i}.0"), format!("{i}.1"), format!("{i}.2")])
+            .collect();
+        assert_eq!(vec, expected);
+    }
+
+    #[test]
+    fn removes_and_replaces() {
+        let mut vec: Vec<String> = (0..6).map(|i| i.to_string()).collect();
+        vec.shrink_to_fit();
+        let mut cursor = cursor_mut(&mut vec);
+        while let Some(mut slot) = cursor.next() {
+            match slot.get().parse::<u32>().unwrap() % 3 {
+                0 => assert_eq!(slot.remove().len(), 1),
+                1 => {
+                    let old = slot.replace(String::from("x"));
+                    slot.insert_after(old);
+                }
+                _ => {}
+            }
+        }
+        drop(cursor);
+        assert_eq!(vec, ["x", "1", "2", "x", "4", "5"]);
+    }
+
+    #[test]
+    fn remove_after_inserts() {
+        let mut vec = vec![String::from("a"), String::from("b")];
+        vec.shrink_to_fit();
+        let mut cursor = cursor_mut(&mut vec);
+        let mut slot = cursor.next().unwrap();
+        slot.insert_after(String::from("c"));
+        slot.insert_after(String::from("d"));
+        assert_eq!(slot.remove(), "a");
+        drop(cursor);
+        assert_eq!(vec, ["c", "d", "b"]);
+    }
+
+    #[test]
+    fn early_drop_keeps_unvisited_elements() {
+        let rc = Rc::new(());
+        let mut vec = vec![Rc::clone(&rc); 5];
+        vec.shrink_to_fit();
+        let mut cursor = cursor_mut(&mut vec);
+        let mut slot = cursor.next().unwrap();
+        slot.insert_after(Rc::clone(&rc));
+        drop(slot.remove());
+        cursor.next().unwrap().insert_after(Rc::clone(&rc));
+        drop(cursor);
+        assert_eq!(vec.len(), 6);
+        assert_eq!(Rc::strong_count(&rc), 7);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let mut vec = vec![(); 3];
+        let mut cursor = cursor_mut(&mut vec);
+        while let Some(mut slot) = cursor.next() {
+            slot.insert_after(());
+        }
+        drop(cursor);
+        assert_eq!(vec.len(), 6);
+    }
+}
@@ -0,0 +1,384 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{
+    io::{self, ErrorKind, Read},
+    vec,
+    vec::Vec,
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Encoding of the length prefixing every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefix {
+    U8,
+    U16Be,
+    U16Le,
+    U32Be,
+    U32Le,
+    /// Unsigned LEB128 variable-length integer.
+    Varint,
+}
+
+impl Prefix {
+    /// Decodes the prefix at the start of `bytes` into its own length and the length of the
+    /// payload following it, or `None` if `bytes` is too short to hold the whole prefix.
+    fn decode(self, bytes: &[u8]) -> Result<Option<(usize, usize)>, FrameError> {
+        fn fixed<const N: usize>(
+            bytes: &[u8],
+            decode: fn([u8; N]) -> u32,
+        ) -> Option<(usize, u32)> {
+            let prefix = bytes.get(..N)?.try_into().ok()?;
+            Some((N, decode(prefix)))
+        }
+        let prefix = match self {
+            Prefix::U8 => fixed(bytes, |[len]| len.into()),
+            Prefix::U16Be => fixed(bytes, |prefix| u16::from_be_bytes(prefix).into()),
+            Prefix::U16Le => fixed(bytes, |prefix| u16::from_le_bytes(prefix).into()),
+            Prefix::U32Be => fixed(bytes, u32::from_be_bytes),
+            Prefix::U32Le => fixed(bytes, u32::from_le_bytes),
+            Prefix::Varint => return decode_varint(bytes),
+        };
+        let Some((header, len)) = prefix else {
+            return Ok(None);
+        };
+        let len = usize::try_from(len).map_err(|_| FrameError::Overflow)?;
+        Ok(Some((header, len)))
+    }
+}
+
+/// Length of the longest varint prefix, enough to encode any `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+fn decode_varint(bytes: &[u8]) -> Result<Option<(usize, usize)>, FrameError> {
+    let mut len = 0usize;
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        let (bits, shift) = (usize::from(byte & 0x7f), (7 * i).try_into().unwrap_or(u32::MAX));
+        match bits.checked_shl(shift) {
+            Some(shifted) if shifted >> shift == bits => len |= shifted,
+            _ if bits == 0 => {}
+            _ => return Err(FrameError::Malformed),
+        }
+        if byte & 0x80 == 0 {
+            return Ok(Some((i + 1, len)));
+        }
+    }
+    match bytes.len() {
+        ..MAX_VARINT_LEN => Ok(None),
+        _ => Err(FrameError::Malformed),
+    }
+}
+
+/// Error lent by frame decoders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// The input ended in the middle of a frame or of its prefix.
+    Truncated,
+    /// The length of a frame does not fit in a `usize`.
+    Overflow,
+    /// A varint prefix is longer than 10 bytes or its value does not fit in a `usize`.
+    Malformed,
+    /// The length of a frame is greater than the configured maximum.
+    TooLong(usize),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Truncated => f.write_str("truncated frame"),
+            FrameError::Overflow => f.write_str("frame length overflows usize"),
+            FrameError::Malformed => f.write_str("malformed varint frame length"),
+            FrameError::TooLong(len) => write!(f, "frame of {len} bytes exceeds maximum length"),
+        }
+    }
+}
+
+impl core::error::Error for FrameError {}
+
+#[cfg(feature = "std")]
+impl From<FrameError> for io::Error {
+    fn from(e: FrameError) -> Self {
+        let kind = match e {
+            FrameError::Truncated => ErrorKind::UnexpectedEof,
+            FrameError::Overflow | FrameError::Malformed | FrameError::TooLong(_) => {
+                ErrorKind::InvalidData
+            }
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+/// Lends the payload of every length-prefixed frame of a buffer in place.
+///
+/// Created by [`frames`].
+pub struct Frames<'s> {
+    buf: &'s mut [u8],
+    prefix: Prefix,
+    pos: usize,
+    done: bool,
+}
+
+impl Frames<'_> {
+    /// Offset in the buffer of the next frame.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+#[gat]
+impl<'s> LendingIter for Frames<'s> {
+    type Item<'a> = Result<&'a mut [u8], FrameError>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done || self.pos == self.buf.len() {
+            return None;
+        }
+        let rest = &mut self.buf[self.pos..];
+        match self.prefix.decode(rest) {
+            Ok(Some((header, len))) if len <= rest.len() - header => {
+                self.pos += header + len;
+                Some(Ok(&mut rest[header..header + len]))
+            }
+            Ok(_) => {
+                self.done = true;
+                Some(Err(FrameError::Truncated))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Default maximum length of the frames lent by [`read_frames`], 8 MiB.
+#[cfg(feature = "std")]
+pub const DEFAULT_MAX_LEN: usize = 8 << 20;
+
+/// Lends the payload of every length-prefixed frame read from a reader, reusing a buffer
+/// that grows as needed to fit the largest frame.
+///
+/// Created by [`read_frames`].
+#[cfg(feature = "std")]
+pub struct ReadFrames<R> {
+    reader: R,
+    prefix: Prefix,
+    max_len: usize,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    consumed: usize,
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R> ReadFrames<R> {
+    /// Fails with [`FrameError::TooLong`] on frames longer than `max_len` bytes instead of
+    /// growing the buffer to fit them, [`DEFAULT_MAX_LEN`] by default.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+#[gat]
+impl<R: Read> LendingIter for ReadFrames<R> {
+    type Item<'a> = io::Result<&'a mut [u8]>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done {
+            return None;
+        }
+        self.start += core::mem::take(&mut self.consumed);
+        loop {
+            let available = &self.buf[self.start..self.end];
+            // Number of bytes needed to make progress decoding the next frame
+            let needed = match self.prefix.decode(available) {
+                Ok(Some((_, len))) if len > self.max_len => Err(FrameError::TooLong(len)),
+                Ok(Some((header, len))) if len <= available.len() - header => {
+                    let start = self.start + header;
+                    self.consumed = header + len;
+                    return Some(Ok(&mut self.buf[start..start + len]));
+                }
+                Ok(Some((header, len))) => header.checked_add(len).ok_or(FrameError::Overflow),
+                Ok(None) => Ok(available.len() + 1),
+                Err(e) => Err(e),
+            };
+            let needed = match needed {
+                Ok(needed) => needed,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            };
+            if needed > self.buf.len() - self.start {
+                self.buf.copy_within(self.start..self.end, 0);
+                self.end -= self.start;
+                self.start = 0;
+            }
+            // The buffer only grows once full, so that its size follows the bytes actually
+            // read rather than the length declared by the prefix
+            if self.end == self.buf.len() {
+                let len = self.buf.len();
+                self.buf.resize(needed.min(2 * len), 0);
+            }
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => {
+                    self.done = true;
+                    return (self.start < self.end).then(|| Err(FrameError::Truncated.into()));
+                }
+                Ok(len) => self.end += len,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Lends the payload of every frame of `buf`, each made of a length encoded as `prefix`
+/// followed by that many bytes.
+///
+/// A frame or prefix running past the end of `buf` is lent as [`FrameError::Truncated`],
+/// after which nothing more is lent.
+pub fn frames(buf: &mut [u8], prefix: Prefix) -> Frames<'_> {
+    Frames {
+        buf,
+        prefix,
+        pos: 0,
+        done: false,
+    }
+}
+
+/// Lends the payload of every frame read from `reader`, each made of a length encoded as
+/// `prefix` followed by that many bytes.
+///
+/// Reads interrupted by [`ErrorKind::Interrupted`] are retried, other errors are lent.
+/// Malformed frames and a frame cut short by the end of the reader are lent as errors
+/// converted from [`FrameError`], after which nothing more is lent. Frames longer than
+/// [`DEFAULT_MAX_LEN`] are rejected unless [`max_len`](ReadFrames::max_len) is raised.
+#[cfg(feature = "std")]
+pub fn read_frames<R: Read>(reader: R, prefix: Prefix) -> ReadFrames<R> {
+    ReadFrames {
+        reader,
+        prefix,
+        max_len: DEFAULT_MAX_LEN,
+        buf: vec![0; 4096],
+        start: 0,
+        end: 0,
+        consumed: 0,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frames, FrameError, Prefix};
+    use crate::prelude::LendingIter;
+
+    #[test]
+    fn frames_in_place() {
+        let mut buf = [2, b'a', b'b', 0, 1, b'c'];
+        let mut iter = frames(&mut buf, Prefix::U8);
+        assert_eq!(iter.next(), Some(Ok(&mut b"ab".to_owned()[..])));
+        assert_eq!(iter.next(), Some(Ok(&mut [][..])));
+        assert_eq!(iter.next(), Some(Ok(&mut b"c".to_owned()[..])));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn overlong_varint_in_place() {
+        let mut buf = [0x80; 11];
+        assert_eq!(frames(&mut buf, Prefix::Varint).next(), Some(Err(FrameError::Malformed)));
+        // Non-canonical but short enough prefixes are accepted
+        let mut buf = [0x81, 0x80, 0x00, b'a'];
+        assert_eq!(frames(&mut buf, Prefix::Varint).next(), Some(Ok(&mut b"a".to_owned()[..])));
+        // The tenth byte of a prefix holds the 64th bit only
+        let mut buf = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+        assert_eq!(frames(&mut buf, Prefix::Varint).next(), Some(Err(FrameError::Malformed)));
+    }
+
+    #[cfg(feature = "std")]
+    mod read {
+        use std::io::{self, ErrorKind, Read};
+
+        use super::super::{read_frames, DEFAULT_MAX_LEN};
+        use super::{FrameError, Prefix};
+        use crate::prelude::LendingIter;
+
+        /// Reader giving out one byte at a time.
+        struct Trickle<'a>(&'a [u8]);
+
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let Some((&first, rest)) = self.0.split_first() else {
+                    return Ok(0);
+                };
+                buf[0] = first;
+                self.0 = rest;
+                Ok(1)
+            }
+        }
+
+        fn error(frame: Option<io::Result<&mut [u8]>>) -> (ErrorKind, FrameError) {
+            let e = frame.unwrap().unwrap_err();
+            (e.kind(), *e.get_ref().unwrap().downcast_ref::<FrameError>().unwrap())
+        }
+
+        #[test]
+        fn frames_across_reads() {
+            let input = [0, 0, 0, 3, b'a', b'b', b'c', 0, 0, 0, 1, b'd'];
+            let mut iter = read_frames(Trickle(&input), Prefix::U32Be);
+            assert_eq!(iter.next().unwrap().unwrap(), b"abc");
+            assert_eq!(iter.next().unwrap().unwrap(), b"d");
+            assert!(iter.next().is_none());
+        }
+
+        #[test]
+        fn frame_larger_than_buffer() {
+            let mut input = vec![0x90, 0x4e];
+            input.extend((0..10_000u32).map(|i| i as u8));
+            input.extend([1, b'x']);
+            let mut iter = read_frames(Trickle(&input), Prefix::Varint);
+            assert_eq!(iter.next().unwrap().unwrap(), &input[2..10_002]);
+            assert_eq!(iter.next().unwrap().unwrap(), b"x");
+            assert!(iter.next().is_none());
+        }
+
+        #[test]
+        fn declared_length_over_default_maximum() {
+            let input = [0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+            let mut iter = read_frames(&input[..], Prefix::U32Be);
+            let len = 0xffff_ffff;
+            assert!(len > DEFAULT_MAX_LEN);
+            assert_eq!(error(iter.next()), (ErrorKind::InvalidData, FrameError::TooLong(len)));
+            assert!(iter.next().is_none());
+        }
+
+        #[test]
+        fn declared_length_is_not_allocated_upfront() {
+            let input = [0xff, 0xff, 0xff, 0xff, 1, 2, 3];
+            let mut iter = read_frames(&input[..], Prefix::U32Be).max_len(usize::MAX);
+            assert_eq!(error(iter.next()), (ErrorKind::UnexpectedEof, FrameError::Truncated));
+            assert!(iter.buf.len() <= 4096);
+        }
+
+        #[test]
+        fn overlong_varint() {
+            let mut iter = read_frames(io::repeat(0x80), Prefix::Varint).max_len(16);
+            assert_eq!(error(iter.next()), (ErrorKind::InvalidData, FrameError::Malformed));
+            assert!(iter.next().is_none());
+        }
+    }
+}
@@ -1,9 +1,16 @@
-use core::iter::{IntoIterator, Iterator};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::Iterator;
 
 use nougat::gat;
 
+#[cfg(feature = "alloc")]
+use super::windows::WindowsLending;
+use super::windows::{ArrayWindowBuf, ArrayWindowsLending};
+
 #[gat(Item)]
 use crate::lending_iter::LendingIter;
+use crate::lending_iter::LendedItem;
 
 pub struct LendingWrapper<I: Iterator> {
     iter: I,
@@ -17,12 +24,66 @@ impl<I: Iterator> LendingIter for LendingWrapper<I> {
     fn next(&mut self) -> Option<Self::Item<'_>> {
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn try_fold<B, E, F>(&mut self, init: B, f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> Result<B, E>,
+    {
+        self.iter.try_fold(init, f)
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        Self: Sized,
+        F: for<'b> FnMut(B, LendedItem<'b, Self>) -> B,
+    {
+        self.iter.fold(init, f)
+    }
 }
 
 pub trait IntoLending {
     type LendingT: LendingIter;
 
     fn lending(self) -> Self::LendingT;
+
+    /// Lends every window of `n` consecutive items as a slice, reusing a single buffer.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    #[cfg(feature = "alloc")]
+    fn windows_lending(self, n: usize) -> WindowsLending<Self>
+    where
+        Self: Iterator + Sized,
+    {
+        assert!(n > 0, "window size must be non-zero");
+        WindowsLending {
+            iter: self,
+            n,
+            buf: Vec::with_capacity(2 * n),
+            head: 0,
+        }
+    }
+
+    /// Same as [`windows_lending`](IntoLending::windows_lending) with the window size known
+    /// at compile time, which does not require `alloc`.
+    ///
+    /// # Panics
+    /// Panics if `N` is zero.
+    fn array_windows_lending<const N: usize>(self) -> ArrayWindowsLending<Self, N>
+    where
+        Self: Iterator + Sized,
+    {
+        assert!(N > 0, "window size must be non-zero");
+        ArrayWindowsLending {
+            iter: self,
+            window: ArrayWindowBuf::new(),
+        }
+    }
 }
 
 impl<I: Iterator> IntoLending for I {
@@ -1,3 +1,29 @@
+#[cfg(feature = "alloc")]
+pub mod backtrack;
+pub mod cartesian_product;
+pub mod columns;
+pub mod combinations;
+#[cfg(feature = "std")]
+pub mod csv;
+#[cfg(feature = "alloc")]
+pub mod cursor;
 pub mod empty;
+pub mod frames;
 pub mod from_fn;
 pub mod from_iter;
+pub mod pairs;
+pub mod partitions;
+pub mod permutations;
+#[cfg(feature = "std")]
+pub mod read;
+pub mod split;
+#[cfg(feature = "alloc")]
+pub mod subsets;
+#[cfg(feature = "alloc")]
+pub mod tree;
+#[cfg(feature = "alloc")]
+pub mod utf8;
+#[cfg(feature = "std")]
+pub mod walk_dir;
+pub mod windows;
+pub mod windows2d;
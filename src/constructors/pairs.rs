@@ -0,0 +1,76 @@
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Lends mutable references to every pair of distinct elements of a slice.
+///
+/// Created by [`pairs_mut`].
+pub struct PairsMut<'s, T> {
+    slice: &'s mut [T],
+    i: usize,
+    j: usize,
+}
+
+#[gat]
+impl<'s, T> LendingIter for PairsMut<'s, T> {
+    type Item<'a> = (&'a mut T, &'a mut T)
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.j >= self.slice.len() {
+            self.i += 1;
+            self.j = self.i + 1;
+        }
+        if self.j >= self.slice.len() {
+            return None;
+        }
+        let (left, right) = self.slice.split_at_mut(self.j);
+        self.j += 1;
+        Some((&mut left[self.i], &mut right[0]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len();
+        let after = len.saturating_sub(self.i + 1);
+        let remaining = len.saturating_sub(self.j) + after * after.saturating_sub(1) / 2;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Lends mutable references to every pair of adjacent elements of a slice.
+///
+/// Created by [`neighbors_mut`].
+pub struct NeighborsMut<'s, T> {
+    slice: &'s mut [T],
+    i: usize,
+}
+
+#[gat]
+impl<'s, T> LendingIter for NeighborsMut<'s, T> {
+    type Item<'a> = (&'a mut T, &'a mut T)
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let (a, b) = self.slice.get_mut(self.i..self.i + 2)?.split_at_mut(1);
+        self.i += 1;
+        Some((&mut a[0], &mut b[0]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.slice.len().saturating_sub(self.i + 1);
+        (remaining, Some(remaining))
+    }
+}
+
+/// Lends `(&mut slice[i], &mut slice[j])` for every `i < j`, ordered by `i` then `j`.
+pub fn pairs_mut<T>(slice: &mut [T]) -> PairsMut<'_, T> {
+    PairsMut { slice, i: 0, j: 1 }
+}
+
+/// Lends `(&mut slice[i], &mut slice[i + 1])` for every `i`.
+pub fn neighbors_mut<T>(slice: &mut [T]) -> NeighborsMut<'_, T> {
+    NeighborsMut { slice, i: 0 }
+}
@@ -0,0 +1,257 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use nougat::gat;
+
+use super::combinations::binomial;
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Lends every partition of an integer into non-increasing positive parts, in reverse
+/// lexicographic order, e.g. `[4]`, `[3, 1]`, `[2, 2]`, `[2, 1, 1]`, `[1, 1, 1, 1]` for 4.
+pub struct Partitions<B> {
+    buf: B,
+    len: usize,
+    first: bool,
+}
+
+#[gat]
+impl<B: AsMut<[usize]>> LendingIter for Partitions<B> {
+    type Item<'a> = &'a [usize]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let parts = self.buf.as_mut();
+        if self.first {
+            self.first = false;
+            return Some(&parts[..self.len]);
+        }
+        // Take one from the last part greater than one, and spread it along with the ones
+        // following it into parts as large as the decremented part allows.
+        let i = parts[..self.len].iter().rposition(|&part| part > 1)?;
+        let mut rest = self.len - i;
+        parts[i] -= 1;
+        let max = parts[i];
+        let mut j = i + 1;
+        while rest > max {
+            parts[j] = max;
+            rest -= max;
+            j += 1;
+        }
+        parts[j] = rest;
+        self.len = j + 1;
+        Some(&parts[..self.len])
+    }
+}
+
+/// Lends every composition of an integer into a fixed number of positive parts, in
+/// lexicographic order, e.g. `[1, 3]`, `[2, 2]`, `[3, 1]` for 4 into 2 parts.
+pub struct Compositions<B> {
+    buf: B,
+    n: usize,
+    first: bool,
+    done: bool,
+}
+
+impl<B: AsRef<[usize]>> Compositions<B> {
+    /// Number of compositions left to lend, or `None` if it overflows.
+    fn remaining(&self) -> Option<usize> {
+        if self.done {
+            return Some(0);
+        }
+        let parts = self.buf.as_ref();
+        let Some(k) = parts.len().checked_sub(1).filter(|&k| k > 0) else {
+            return Some(self.first as usize);
+        };
+        // Compositions are in bijection with the positions of the k - 1 cuts among the
+        // n - 1 gaps between units, in the same order, which are ranked as combinations.
+        let mut cut = 0;
+        let after = parts[..k].iter().enumerate().try_fold(0usize, |acc, (j, &part)| {
+            cut += part;
+            acc.checked_add(binomial(self.n - 1 - cut, k - j)?)
+        })?;
+        match self.first {
+            true => after.checked_add(1),
+            false => Some(after),
+        }
+    }
+}
+
+#[gat]
+impl<B: AsRef<[usize]> + AsMut<[usize]>> LendingIter for Compositions<B> {
+    type Item<'a> = &'a [usize]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done {
+            return None;
+        }
+        let parts = self.buf.as_mut();
+        if self.first {
+            self.first = false;
+            return Some(parts);
+        }
+        // Increment the last part that can take one from the parts following it, and
+        // leave everything else in the last part.
+        let k = parts.len();
+        let mut rest = 0;
+        for i in (1..k).rev() {
+            rest += parts[i];
+            if rest > k - i {
+                parts[i - 1] += 1;
+                parts[i..].fill(1);
+                parts[k - 1] = rest - (k - i);
+                return Some(parts);
+            }
+        }
+        self.done = true;
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Lends every partition of `n`.
+#[cfg(feature = "alloc")]
+pub fn partitions(n: usize) -> Partitions<Vec<usize>> {
+    partitions_in(vec![0; n])
+}
+
+/// Lends every partition of `buf.len()`, using `buf` to hold the parts, which does not
+/// require `alloc`.
+pub fn partitions_in<B: AsMut<[usize]>>(mut buf: B) -> Partitions<B> {
+    let parts = buf.as_mut();
+    let (n, len) = (parts.len(), parts.len().min(1));
+    if let Some(first) = parts.first_mut() {
+        *first = n;
+    }
+    Partitions {
+        buf,
+        len,
+        first: true,
+    }
+}
+
+/// Lends every composition of `n` into `k` parts.
+#[cfg(feature = "alloc")]
+pub fn compositions(n: usize, k: usize) -> Compositions<Vec<usize>> {
+    compositions_in(n, vec![0; k])
+}
+
+/// Lends every composition of `n` into `buf.len()` parts, using `buf` to hold the parts,
+/// which does not require `alloc`.
+pub fn compositions_in<B: AsMut<[usize]>>(n: usize, mut buf: B) -> Compositions<B> {
+    let parts = buf.as_mut();
+    let k = parts.len();
+    let done = if k == 0 { n > 0 } else { k > n };
+    if !done {
+        parts.fill(1);
+        if let Some(last) = parts.last_mut() {
+            *last = n - (k - 1);
+        }
+    }
+    Compositions {
+        buf,
+        n,
+        first: true,
+        done,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compositions_in, partitions_in};
+    use crate::prelude::LendingIter;
+
+    /// Every `k`-tuple of positive parts adding up to `n`, in lexicographic order.
+    fn brute_force(n: usize, k: usize) -> Vec<Vec<usize>> {
+        if k == 0 {
+            return match n {
+                0 => vec![vec![]],
+                _ => vec![],
+            };
+        }
+        (1..=n)
+            .flat_map(|first| {
+                let rest = brute_force(n - first, k - 1);
+                rest.into_iter().map(move |rest| [&[first][..], &rest].concat())
+            })
+            .collect()
+    }
+
+    fn check(n: usize, k: usize) {
+        let expected = brute_force(n, k);
+        let mut compositions = compositions_in(n, vec![0; k]);
+        let mut seen = Vec::new();
+        loop {
+            let left = expected.len() - seen.len();
+            assert_eq!(compositions.size_hint(), (left, Some(left)), "{n} into {k}: {seen:?}");
+            let Some(composition) = compositions.next() else { break };
+            seen.push(composition.to_vec());
+        }
+        assert_eq!(seen, expected, "{n} into {k}");
+        assert!(compositions.next().is_none());
+        assert_eq!(compositions.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn compositions_against_brute_force() {
+        for n in 0..=7 {
+            for k in 0..=n + 2 {
+                check(n, k);
+            }
+        }
+    }
+
+    #[test]
+    fn compositions_without_alloc() {
+        let mut compositions = compositions_in(4, [0; 2]);
+        assert_eq!(compositions.size_hint(), (3, Some(3)));
+        assert_eq!(compositions.next(), Some(&[1, 3][..]));
+        assert_eq!(compositions.next(), Some(&[2, 2][..]));
+        assert_eq!(compositions.next(), Some(&[3, 1][..]));
+        assert_eq!(compositions.next(), None);
+        assert_eq!(compositions_in(0, []).count(), 1);
+        assert_eq!(compositions_in(0, [0; 1]).count(), 0);
+        assert_eq!(compositions_in(3, []).count(), 0);
+    }
+
+    #[test]
+    fn size_hint_of_many_compositions() {
+        assert_eq!(compositions_in(200, vec![0; 100]).size_hint(), (usize::MAX, None));
+        #[cfg(target_pointer_width = "64")]
+        {
+            // C(66, 33) compositions of 67 into 34 parts
+            let count = 7219428434016265740;
+            let compositions = compositions_in(67, vec![0; 34]);
+            assert_eq!(compositions.size_hint(), (count, Some(count)));
+        }
+    }
+
+    #[test]
+    fn partitions() {
+        // Number of partitions of 0 to 10
+        let counts = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42];
+        for (n, &count) in counts.iter().enumerate() {
+            let mut partitions = partitions_in(vec![0; n]);
+            let mut seen: Vec<Vec<usize>> = Vec::new();
+            while let Some(partition) = partitions.next() {
+                assert_eq!(partition.iter().sum::<usize>(), n);
+                assert!(partition.iter().all(|&part| part > 0));
+                assert!(partition.is_sorted_by(|a, b| a >= b));
+                if let Some(last) = seen.last() {
+                    assert!(last[..] > *partition, "{last:?} then {partition:?}");
+                }
+                seen.push(partition.to_vec());
+            }
+            assert_eq!(seen.len(), count, "partitions of {n}");
+        }
+    }
+}
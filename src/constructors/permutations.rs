@@ -0,0 +1,231 @@
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Lends every permutation of a slice, permuting it in place using Heap's algorithm.
+///
+/// Once exhausted, the slice is left in the order of the last permutation.
+pub struct PermutationsMut<'s, T> {
+    slice: &'s mut [T],
+    count: usize,
+    done: bool,
+}
+
+impl<T> PermutationsMut<'_, T> {
+    /// Pair of positions to swap to get from the last lent permutation to the next one,
+    /// or `None` if it was the last permutation.
+    ///
+    /// The iterative form of Heap's algorithm keeps one counter per prefix length; these
+    /// counters are exactly the digits of the number of swaps made so far in the factorial
+    /// number system, so they are recomputed from it rather than stored.
+    fn next_swap(&self) -> Option<(usize, usize)> {
+        let mut rest = self.count - 1;
+        for i in 1..self.slice.len() {
+            let digit = rest % (i + 1);
+            rest /= i + 1;
+            if digit < i {
+                return Some(match i % 2 {
+                    0 => (0, i),
+                    _ => (digit, i),
+                });
+            }
+        }
+        None
+    }
+}
+
+#[gat]
+impl<'s, T> LendingIter for PermutationsMut<'s, T> {
+    type Item<'a> = &'a [T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done {
+            return None;
+        }
+        if self.count > 0 {
+            match self.next_swap() {
+                Some((a, b)) => self.slice.swap(a, b),
+                None => {
+                    self.done = true;
+                    return None;
+                }
+            }
+        }
+        self.count += 1;
+        Some(self.slice)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match (1..=self.slice.len()).try_fold(1usize, |acc, k| acc.checked_mul(k)) {
+            Some(total) => (total - self.count, Some(total - self.count)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+pub fn permutations_mut<T>(slice: &mut [T]) -> PermutationsMut<'_, T> {
+    PermutationsMut {
+        slice,
+        count: 0,
+        done: false,
+    }
+}
+
+/// Lends every distinct permutation of a slice in lexicographic order, permuting it in
+/// place. Equal elements are never swapped with each other, so no permutation is lent
+/// twice.
+///
+/// Once exhausted, the slice is left in the order of the last permutation.
+pub struct LexicographicPermutations<'s, T: Ord> {
+    slice: &'s mut [T],
+    prev: bool,
+    sorted: bool,
+    first: bool,
+    done: bool,
+}
+
+impl<'s, T: Ord> LexicographicPermutations<'s, T> {
+    /// Starts at the current arrangement of `slice` instead of sorting it first, so only
+    /// the permutations following it are lent.
+    pub fn from_current(slice: &'s mut [T]) -> Self {
+        LexicographicPermutations {
+            slice,
+            prev: false,
+            sorted: false,
+            first: true,
+            done: false,
+        }
+    }
+
+    /// Lends permutations in decreasing lexicographic order instead.
+    ///
+    /// If called before the first permutation is lent by an iterator created with
+    /// [`lexicographic_permutations`], the slice is sorted in decreasing order so that
+    /// every permutation is still lent.
+    pub fn prev(mut self) -> Self {
+        if self.sorted && self.first {
+            self.slice.reverse();
+        }
+        self.prev = !self.prev;
+        self
+    }
+}
+
+/// Rearranges `slice` into the next permutation in lexicographic order, returning `false`
+/// and leaving it untouched if it is the last one.
+fn next_permutation<T: Ord>(slice: &mut [T]) -> bool {
+    let Some(i) = (1..slice.len()).rev().find(|&i| slice[i - 1] < slice[i]) else {
+        return false;
+    };
+    let j = (i..slice.len()).rev().find(|&j| slice[i - 1] < slice[j]).unwrap();
+    slice.swap(i - 1, j);
+    slice[i..].reverse();
+    true
+}
+
+/// Rearranges `slice` into the previous permutation in lexicographic order, returning
+/// `false` and leaving it untouched if it is the first one.
+fn prev_permutation<T: Ord>(slice: &mut [T]) -> bool {
+    let Some(i) = (1..slice.len()).rev().find(|&i| slice[i - 1] > slice[i]) else {
+        return false;
+    };
+    let j = (i..slice.len()).rev().find(|&j| slice[i - 1] > slice[j]).unwrap();
+    slice.swap(i - 1, j);
+    slice[i..].reverse();
+    true
+}
+
+#[gat]
+impl<'s, T: Ord> LendingIter for LexicographicPermutations<'s, T> {
+    type Item<'a> = &'a [T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.done {
+            return None;
+        }
+        if self.first {
+            self.first = false;
+            return Some(self.slice);
+        }
+        let advanced = match self.prev {
+            false => next_permutation(self.slice),
+            true => prev_permutation(self.slice),
+        };
+        match advanced {
+            true => Some(self.slice),
+            false => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+pub fn lexicographic_permutations<T: Ord>(slice: &mut [T]) -> LexicographicPermutations<'_, T> {
+    slice.sort_unstable();
+    LexicographicPermutations {
+        slice,
+        prev: false,
+        sorted: true,
+        first: true,
+        done: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::permutations_mut;
+    use crate::prelude::LendingIter;
+
+    #[test]
+    fn every_permutation_once() {
+        let mut factorial = 1;
+        for n in 0..=6 {
+            factorial *= n.max(1);
+            let mut slice: Vec<usize> = (0..n).collect();
+            let mut permutations = permutations_mut(&mut slice);
+            let mut seen = HashSet::new();
+            let mut last: Option<Vec<usize>> = None;
+            loop {
+                let left = factorial - seen.len();
+                assert_eq!(permutations.size_hint(), (left, Some(left)), "n = {n}");
+                let Some(permutation) = permutations.next() else { break };
+                let mut sorted = permutation.to_vec();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+                if let Some(last) = &last {
+                    // Heap's algorithm moves from one permutation to the next with a swap
+                    let moved = last.iter().zip(permutation).filter(|(a, b)| a != b).count();
+                    assert_eq!(moved, 2);
+                }
+                assert!(seen.insert(permutation.to_vec()), "{permutation:?} lent twice");
+                last = Some(permutation.to_vec());
+            }
+            assert_eq!(seen.len(), factorial);
+            assert!(permutations.next().is_none());
+            assert_eq!(permutations.size_hint(), (0, Some(0)));
+        }
+    }
+
+    #[test]
+    fn size_hint_overflowing_factorial() {
+        let mut slice = [(); 30];
+        assert_eq!(permutations_mut(&mut slice).size_hint(), (usize::MAX, None));
+        #[cfg(target_pointer_width = "64")]
+        {
+            let mut slice = [(); 20];
+            let count = (1..=20).product();
+            assert_eq!(permutations_mut(&mut slice).size_hint(), (count, Some(count)));
+        }
+    }
+}
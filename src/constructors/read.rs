@@ -0,0 +1,154 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, ErrorKind, Read},
+    string::ToString,
+    vec,
+    vec::Vec,
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Lends the bytes returned by each successful read from a reader, reusing a single buffer.
+///
+/// Created by [`read_chunks`].
+pub struct ReadChunks<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R> ReadChunks<R> {
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[gat]
+impl<R: Read> LendingIter for ReadChunks<R> {
+    type Item<'a> = io::Result<&'a [u8]>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        loop {
+            match self.reader.read(&mut self.buf) {
+                Ok(0) => return None,
+                Ok(len) => return Some(Ok(&self.buf[..len])),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Error lent by [`ReadExactRecords`].
+#[derive(Debug)]
+pub enum RecordError<'a> {
+    Io(io::Error),
+    /// The reader ended in the middle of a record, after these bytes.
+    Partial(&'a [u8]),
+}
+
+impl fmt::Display for RecordError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io(e) => e.fmt(f),
+            RecordError::Partial(bytes) => {
+                write!(f, "trailing partial record of {} bytes", bytes.len())
+            }
+        }
+    }
+}
+
+impl Error for RecordError<'_> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RecordError::Io(e) => Some(e),
+            RecordError::Partial(_) => None,
+        }
+    }
+}
+
+impl From<RecordError<'_>> for io::Error {
+    fn from(e: RecordError<'_>) -> Self {
+        match e {
+            RecordError::Io(e) => e,
+            RecordError::Partial(_) => io::Error::new(ErrorKind::UnexpectedEof, e.to_string()),
+        }
+    }
+}
+
+/// Lends the fixed-size records making up the contents of a reader, reusing a single
+/// buffer.
+///
+/// Created by [`read_exact_records`].
+pub struct ReadExactRecords<R, const N: usize> {
+    reader: R,
+    buf: [u8; N],
+    filled: usize,
+}
+
+impl<R, const N: usize> ReadExactRecords<R, N> {
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[gat]
+impl<R: Read, const N: usize> LendingIter for ReadExactRecords<R, N> {
+    type Item<'a> = Result<&'a [u8; N], RecordError<'a>>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        // Bytes read before an error are kept, so that reading resumes where it stopped
+        while self.filled < N {
+            match self.reader.read(&mut self.buf[self.filled..]) {
+                Ok(0) => break,
+                Ok(len) => self.filled += len,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(RecordError::Io(e))),
+            }
+        }
+        match core::mem::take(&mut self.filled) {
+            0 => None,
+            filled if filled < N => Some(Err(RecordError::Partial(&self.buf[..filled]))),
+            _ => Some(Ok(&self.buf)),
+        }
+    }
+}
+
+/// Lends the bytes returned by each read from `reader` into a buffer of `buf_size` bytes,
+/// until it reaches the end of its contents.
+///
+/// Reads interrupted by [`ErrorKind::Interrupted`] are retried, other errors are lent.
+///
+/// # Panics
+/// Panics if `buf_size` is zero.
+pub fn read_chunks<R: Read>(reader: R, buf_size: usize) -> ReadChunks<R> {
+    assert!(buf_size > 0, "buffer size must be non-zero");
+    ReadChunks {
+        reader,
+        buf: vec![0; buf_size],
+    }
+}
+
+/// Lends the contents of `reader` as records of `N` bytes, until it reaches the end of its
+/// contents. Trailing bytes not making up a whole record are lent as
+/// [`RecordError::Partial`].
+///
+/// Reads interrupted by [`ErrorKind::Interrupted`] are retried, other errors are lent.
+///
+/// # Panics
+/// Panics if `N` is zero.
+pub fn read_exact_records<const N: usize, R: Read>(reader: R) -> ReadExactRecords<R, N> {
+    assert!(N > 0, "record size must be non-zero");
+    ReadExactRecords {
+        reader,
+        buf: [0; N],
+        filled: 0,
+    }
+}
@@ -0,0 +1,194 @@
+use core::ops::Range;
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Bounds of the segments left to lend, shared by the splitting lenders.
+#[derive(Debug, Clone)]
+struct Segments {
+    start: usize,
+    end: usize,
+    remaining: Option<usize>,
+    rev: bool,
+    skip_empty: bool,
+    done: bool,
+}
+
+impl Segments {
+    fn new(len: usize, skip_empty: bool) -> Self {
+        Segments {
+            start: 0,
+            end: len,
+            remaining: None,
+            rev: false,
+            skip_empty,
+            done: false,
+        }
+    }
+
+    /// Range of the next segment of `items`, separated by the items matching `is_sep`.
+    fn next<T>(&mut self, items: &[T], mut is_sep: impl FnMut(&T) -> bool) -> Option<Range<usize>> {
+        loop {
+            if self.done || self.remaining == Some(0) {
+                return None;
+            }
+            let range = self.start..self.end;
+            let sep = match self.remaining {
+                Some(1) => None,
+                _ if self.rev => items[range.clone()].iter().rposition(&mut is_sep),
+                _ => items[range.clone()].iter().position(&mut is_sep),
+            };
+            let segment = match sep.map(|i| i + range.start) {
+                Some(i) if self.rev => {
+                    self.end = i;
+                    i + 1..range.end
+                }
+                Some(i) => {
+                    self.start = i + 1;
+                    range.start..i
+                }
+                None => {
+                    self.done = true;
+                    if self.skip_empty {
+                        // The rest is lent whole, without its leading or trailing separators
+                        let items = &items[range.clone()];
+                        let first = items.iter().position(|x| !is_sep(x))?;
+                        let last = items.iter().rposition(|x| !is_sep(x)).unwrap_or(first);
+                        range.start + first..range.start + last + 1
+                    } else {
+                        range
+                    }
+                }
+            };
+            if self.skip_empty && segment.is_empty() {
+                continue;
+            }
+            self.remaining = self.remaining.map(|n| n - 1);
+            return Some(segment);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done || self.remaining == Some(0) {
+            return (0, Some(0));
+        }
+        let len = self.end - self.start;
+        let (lower, upper) = if self.skip_empty {
+            (0, len.div_ceil(2))
+        } else {
+            (1, len + 1)
+        };
+        let upper = self.remaining.map_or(upper, |n| n.min(upper));
+        (lower, Some(upper))
+    }
+}
+
+/// Lends mutable references to the segments of a slice separated by the elements matching a
+/// predicate.
+///
+/// Created by [`split_mut`].
+pub struct SplitMut<'s, T, P> {
+    slice: &'s mut [T],
+    pred: P,
+    segments: Segments,
+}
+
+impl<T, P> SplitMut<'_, T, P> {
+    /// Lends the segments starting from the end of the slice.
+    pub fn rev(mut self) -> Self {
+        self.segments.rev = !self.segments.rev;
+        self
+    }
+
+    /// Lends at most `n` segments, the last one holding the rest of the slice, separators
+    /// included, like [`slice::splitn_mut`].
+    pub fn limit(mut self, n: usize) -> Self {
+        self.segments.remaining = Some(n);
+        self
+    }
+}
+
+#[gat]
+impl<'s, T, P> LendingIter for SplitMut<'s, T, P>
+where
+    P: FnMut(&T) -> bool,
+{
+    type Item<'a> = &'a mut [T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let range = self.segments.next(self.slice, &mut self.pred)?;
+        Some(&mut self.slice[range])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.segments.size_hint()
+    }
+}
+
+/// Lends mutable references to the words of a string separated by ASCII whitespace.
+///
+/// Created by [`split_ascii_whitespace_mut`].
+pub struct SplitAsciiWhitespaceMut<'s> {
+    s: &'s mut str,
+    segments: Segments,
+}
+
+impl SplitAsciiWhitespaceMut<'_> {
+    /// Lends the words starting from the end of the string.
+    pub fn rev(mut self) -> Self {
+        self.segments.rev = !self.segments.rev;
+        self
+    }
+
+    /// Lends at most `n` words, the last one holding the rest of the string, inner
+    /// whitespace included but trimmed on both ends.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.segments.remaining = Some(n);
+        self
+    }
+}
+
+#[gat]
+impl<'s> LendingIter for SplitAsciiWhitespaceMut<'s> {
+    type Item<'a> = &'a mut str
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let range = self.segments.next(self.s.as_bytes(), u8::is_ascii_whitespace)?;
+        // ASCII whitespace never lies inside a multibyte character
+        Some(&mut self.s[range])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.segments.size_hint()
+    }
+}
+
+/// Lends `&mut` to every segment of `slice` separated by the elements for which `pred`
+/// returns `true`, the separators being excluded.
+///
+/// Like [`slice::split_mut`], consecutive separators lend empty segments, as do separators
+/// at either end of the slice.
+pub fn split_mut<T, P>(slice: &mut [T], pred: P) -> SplitMut<'_, T, P>
+where
+    P: FnMut(&T) -> bool,
+{
+    let segments = Segments::new(slice.len(), false);
+    SplitMut {
+        slice,
+        pred,
+        segments,
+    }
+}
+
+/// Lends `&mut` to every word of `s`, words being separated by any amount of ASCII
+/// whitespace, like [`str::split_ascii_whitespace`].
+pub fn split_ascii_whitespace_mut(s: &mut str) -> SplitAsciiWhitespaceMut<'_> {
+    let segments = Segments::new(s.len(), true);
+    SplitAsciiWhitespaceMut { s, segments }
+}
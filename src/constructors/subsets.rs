@@ -0,0 +1,215 @@
+use alloc::vec::Vec;
+
+use nougat::gat;
+
+use super::combinations::{binomial, Indices};
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Element added to or removed from the subset, by position in the slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added(usize),
+    Removed(usize),
+}
+
+/// Lends every subset of the elements of a slice in Gray code order, so that each subset
+/// differs from the previous one by a single element. Elements keep their order in the
+/// slice.
+pub struct Subsets<'s, T> {
+    slice: &'s [T],
+    count: usize,
+    positions: Vec<usize>,
+    buf: Vec<&'s T>,
+    last_change: Option<Change>,
+}
+
+impl<T> Subsets<'_, T> {
+    /// Element that changed between the last two lent subsets, or `None` if at most one
+    /// subset was lent.
+    pub fn last_change(&self) -> Option<Change> {
+        self.last_change
+    }
+}
+
+#[gat]
+impl<'s, T> LendingIter for Subsets<'s, T> {
+    type Item<'a> = &'a [&'s T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.count > 0 {
+            // The element toggled at step i of the binary reflected Gray code is the lowest
+            // set bit of i, and the code wraps around once that bit is past the last element.
+            let i = self.count.trailing_zeros() as usize;
+            if i >= self.slice.len() {
+                return None;
+            }
+            self.last_change = Some(match self.positions.binary_search(&i) {
+                Ok(p) => {
+                    self.positions.remove(p);
+                    self.buf.remove(p);
+                    Change::Removed(i)
+                }
+                Err(p) => {
+                    self.positions.insert(p, i);
+                    self.buf.insert(p, &self.slice[i]);
+                    Change::Added(i)
+                }
+            });
+        }
+        self.count += 1;
+        Some(&self.buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.slice.len() >= usize::BITS as usize {
+            return (usize::MAX, None);
+        }
+        let remaining = (1 << self.slice.len()) - self.count;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Lends every subset of the elements of a slice by increasing size, and subsets of the
+/// same size in lexicographic order of their positions. Elements keep their order in the
+/// slice.
+pub struct SubsetsBySize<'s, T> {
+    slice: &'s [T],
+    indices: Indices<Vec<usize>>,
+    buf: Vec<&'s T>,
+}
+
+impl<T> SubsetsBySize<'_, T> {
+    /// Number of subsets left to lend, or `None` if it overflows.
+    fn remaining(&self) -> Option<usize> {
+        let (n, k) = (self.slice.len(), self.indices.indices.len());
+        (k + 1..=n).try_fold(self.indices.remaining()?, |acc, k| {
+            acc.checked_add(binomial(n, k)?)
+        })
+    }
+}
+
+#[gat]
+impl<'s, T> LendingIter for SubsetsBySize<'s, T> {
+    type Item<'a> = &'a [&'s T]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        loop {
+            if let Some(from) = self.indices.advance() {
+                self.buf.truncate(from);
+                self.buf
+                    .extend(self.indices.indices[from..].iter().map(|&i| &self.slice[i]));
+                return Some(&self.buf);
+            }
+            let n = self.slice.len();
+            if self.indices.indices.len() == n {
+                return None;
+            }
+            let mut indices = core::mem::take(&mut self.indices.indices);
+            indices.push(0);
+            self.indices = Indices::new(indices, n, false);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Lends every subset of the elements of `slice` in Gray code order, starting with the
+/// empty one.
+pub fn subsets<T>(slice: &[T]) -> Subsets<'_, T> {
+    Subsets {
+        slice,
+        count: 0,
+        positions: Vec::with_capacity(slice.len()),
+        buf: Vec::with_capacity(slice.len()),
+        last_change: None,
+    }
+}
+
+/// Lends every subset of the elements of `slice` by increasing size, starting with the
+/// empty one.
+pub fn subsets_by_size<T>(slice: &[T]) -> SubsetsBySize<'_, T> {
+    SubsetsBySize {
+        slice,
+        indices: Indices::new(Vec::with_capacity(slice.len()), slice.len(), false),
+        buf: Vec::with_capacity(slice.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{subsets, subsets_by_size, Change};
+    use crate::prelude::LendingIter;
+
+    #[test]
+    fn gray_code_subsets() {
+        let positions: Vec<usize> = (0..6).collect();
+        for n in 0..=6 {
+            let mut subsets = subsets(&positions[..n]);
+            let mut seen = HashSet::new();
+            let mut last: Vec<usize> = Vec::new();
+            loop {
+                let left = (1 << n) - seen.len();
+                assert_eq!(subsets.size_hint(), (left, Some(left)), "n = {n}");
+                let Some(subset) = subsets.next() else { break };
+                let subset: Vec<usize> = subset.iter().map(|&&i| i).collect();
+                assert!(subset.is_sorted());
+                let change = match subset.len() > last.len() {
+                    true => subset.iter().find(|i| !last.contains(i)).map(|&i| Change::Added(i)),
+                    false => last.iter().find(|i| !subset.contains(i)).map(|&i| Change::Removed(i)),
+                };
+                assert_eq!(subsets.last_change(), change);
+                assert!(subset.len().abs_diff(last.len()) <= 1);
+                assert!(seen.insert(subset.clone()), "{subset:?} lent twice");
+                last = subset;
+            }
+            assert_eq!(seen.len(), 1 << n);
+            assert!(subsets.next().is_none());
+            assert_eq!(subsets.size_hint(), (0, Some(0)));
+        }
+    }
+
+    #[test]
+    fn subsets_by_size_against_brute_force() {
+        let positions: Vec<usize> = (0..6).collect();
+        for n in 0..=6 {
+            let mut expected: Vec<Vec<usize>> = (0..1usize << n)
+                .map(|bits| (0..n).filter(|i| bits >> i & 1 == 1).collect())
+                .collect();
+            expected.sort_by(|a: &Vec<usize>, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+            let mut subsets = subsets_by_size(&positions[..n]);
+            let mut seen = Vec::new();
+            loop {
+                let left = expected.len() - seen.len();
+                assert_eq!(subsets.size_hint(), (left, Some(left)), "after {seen:?}");
+                let Some(subset) = subsets.next() else { break };
+                seen.push(subset.iter().map(|&&i| i).collect::<Vec<_>>());
+            }
+            assert_eq!(seen, expected);
+            assert!(subsets.next().is_none());
+        }
+    }
+
+    #[test]
+    fn size_hint_of_many_subsets() {
+        assert_eq!(subsets(&[(); 64]).size_hint(), (usize::MAX, None));
+        assert_eq!(subsets_by_size(&[(); 64]).size_hint(), (usize::MAX, None));
+        #[cfg(target_pointer_width = "64")]
+        {
+            let count = 1 << 63;
+            assert_eq!(subsets(&[(); 63]).size_hint(), (count, Some(count)));
+            assert_eq!(subsets_by_size(&[(); 63]).size_hint(), (count, Some(count)));
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{mem, slice::IterMut};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Gives access to the children of a tree node.
+///
+/// Implemented by every function `fn(&mut N) -> &mut [N]`, such as a method of the node
+/// type. Closures cannot express that the returned slice borrows from the node, so either
+/// pass a named function or implement this trait on a type of your own.
+pub trait Children<N> {
+    fn children<'a>(&mut self, node: &'a mut N) -> &'a mut [N];
+}
+
+impl<N, F> Children<N> for F
+where
+    F: FnMut(&mut N) -> &mut [N],
+{
+    fn children<'a>(&mut self, node: &'a mut N) -> &'a mut [N] {
+        (self)(node)
+    }
+}
+
+struct Frame<'s, N> {
+    /// Node whose children are being visited, only accessed again once they all are.
+    parent: *mut N,
+    children: IterMut<'s, N>,
+}
+
+/// Lends mutable references to every node of a tree in depth-first order, along with their
+/// depth.
+///
+/// Created by [`dfs_mut`].
+pub struct DfsMut<'s, N, C> {
+    children: C,
+    root: Option<&'s mut N>,
+    stack: Vec<Frame<'s, N>>,
+    lent: Option<&'s mut N>,
+    post_order: bool,
+    skip: bool,
+}
+
+impl<'s, N, C: Children<N>> DfsMut<'s, N, C> {
+    /// Lends every node after its children instead of before them.
+    pub fn post_order(mut self) -> Self {
+        self.post_order = true;
+        self
+    }
+
+    /// Does not visit the children of the node that was just lent.
+    ///
+    /// Has no effect in post-order, where they have already been visited.
+    pub fn skip_children(&mut self) {
+        self.skip = !self.post_order;
+    }
+
+    fn push(&mut self, node: &'s mut N) {
+        let parent: *mut N = node;
+        // SAFETY:
+        // `node` is not used anymore, so the children and later the parent itself are only
+        // reached through `parent`, never at the same time
+        let children = self.children.children(unsafe { &mut *parent }).iter_mut();
+        self.stack.push(Frame { parent, children });
+    }
+}
+
+#[gat]
+impl<'s, N, C: Children<N>> LendingIter for DfsMut<'s, N, C> {
+    type Item<'a> = (usize, &'a mut N)
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if let Some(node) = self.lent.take() {
+            if !mem::take(&mut self.skip) {
+                self.push(node);
+            }
+        }
+        loop {
+            let node = match self.root.take() {
+                Some(root) => root,
+                None => {
+                    let frame = self.stack.last_mut()?;
+                    match frame.children.next() {
+                        Some(child) => child,
+                        None => {
+                            let frame = self.stack.pop()?;
+                            if !self.post_order {
+                                continue;
+                            }
+                            // SAFETY:
+                            // The children of the parent have all been visited and their
+                            // iterator dropped, so nothing else borrows from it anymore
+                            return Some((self.stack.len(), unsafe { &mut *frame.parent }));
+                        }
+                    }
+                }
+            };
+            if self.post_order {
+                self.push(node);
+                continue;
+            }
+            let depth = self.stack.len();
+            return Some((depth, self.lent.insert(node)));
+        }
+    }
+}
+
+/// Lends mutable references to every node of a tree in breadth-first order, along with
+/// their depth.
+///
+/// Created by [`bfs_mut`].
+pub struct BfsMut<'s, N, C> {
+    children: C,
+    root: Option<&'s mut N>,
+    queue: VecDeque<(usize, IterMut<'s, N>)>,
+    lent: Option<(usize, &'s mut N)>,
+    skip: bool,
+}
+
+impl<N, C> BfsMut<'_, N, C> {
+    /// Does not visit the children of the node that was just lent.
+    pub fn skip_children(&mut self) {
+        self.skip = true;
+    }
+}
+
+#[gat]
+impl<'s, N, C: Children<N>> LendingIter for BfsMut<'s, N, C> {
+    type Item<'a> = (usize, &'a mut N)
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if let Some((depth, node)) = self.lent.take() {
+            if !mem::take(&mut self.skip) {
+                let children = self.children.children(node);
+                if !children.is_empty() {
+                    self.queue.push_back((depth + 1, children.iter_mut()));
+                }
+            }
+        }
+        let (depth, node) = match self.root.take() {
+            Some(root) => (0, root),
+            None => loop {
+                let (depth, children) = self.queue.front_mut()?;
+                match children.next() {
+                    Some(child) => break (*depth, child),
+                    None => {
+                        self.queue.pop_front();
+                    }
+                }
+            },
+        };
+        let (depth, node) = self.lent.insert((depth, node));
+        Some((*depth, node))
+    }
+}
+
+/// Lends `(depth, &mut node)` for every node of the tree rooted at `root`, in depth-first
+/// pre-order, the root having depth 0.
+///
+/// `children` gives the children of a node, which are only looked up once the node has been
+/// lent, so they can be modified through it beforehand. The traversal keeps an explicit
+/// stack instead of recursing, so the depth of the tree is not limited by the call stack.
+pub fn dfs_mut<N, C: Children<N>>(root: &mut N, children: C) -> DfsMut<'_, N, C> {
+    DfsMut {
+        children,
+        root: Some(root),
+        stack: Vec::new(),
+        lent: None,
+        post_order: false,
+        skip: false,
+    }
+}
+
+/// Lends `(depth, &mut node)` for every node of the tree rooted at `root`, level by level,
+/// the root having depth 0.
+///
+/// As with [`dfs_mut`], the children of a node are only looked up once it has been lent.
+pub fn bfs_mut<N, C: Children<N>>(root: &mut N, children: C) -> BfsMut<'_, N, C> {
+    BfsMut {
+        children,
+        root: Some(root),
+        queue: VecDeque::new(),
+        lent: None,
+        skip: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use super::{bfs_mut, dfs_mut};
+    use crate::prelude::LendingIter;
+
+    struct Node {
+        name: char,
+        size: u32,
+        children: Vec<Node>,
+    }
+
+    fn new_node(name: char, children: Vec<Node>) -> Node {
+        Node {
+            name,
+            size: 1,
+            children,
+        }
+    }
+
+    fn children(node: &mut Node) -> &mut [Node] {
+        &mut node.children
+    }
+
+    /// a -> (b -> (d, e), c -> f)
+    fn tree() -> Node {
+        new_node(
+            'a',
+            vec![
+                new_node('b', vec![new_node('d', vec![]), new_node('e', vec![])]),
+                new_node('c', vec![new_node('f', vec![])]),
+            ],
+        )
+    }
+
+    #[test]
+    fn pre_order() {
+        let mut root = tree();
+        let mut nodes = dfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((depth, node)) = nodes.next() {
+            seen.push((depth, node.name));
+        }
+        assert_eq!(seen, [(0, 'a'), (1, 'b'), (2, 'd'), (2, 'e'), (1, 'c'), (2, 'f')]);
+    }
+
+    #[test]
+    fn post_order() {
+        let mut root = tree();
+        let mut nodes = dfs_mut(&mut root, children).post_order();
+        let mut seen = Vec::new();
+        while let Some((depth, node)) = nodes.next() {
+            // Children are lent before their parent, so their size is already final
+            node.size += node.children.iter().map(|child| child.size).sum::<u32>();
+            seen.push((depth, node.name));
+        }
+        assert_eq!(seen, [(2, 'd'), (2, 'e'), (1, 'b'), (2, 'f'), (1, 'c'), (0, 'a')]);
+        assert_eq!(root.size, 6);
+        assert_eq!(root.children[0].size, 3);
+    }
+
+    #[test]
+    fn breadth_first() {
+        let mut root = tree();
+        let mut nodes = bfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((depth, node)) = nodes.next() {
+            seen.push((depth, node.name));
+        }
+        assert_eq!(seen, [(0, 'a'), (1, 'b'), (1, 'c'), (2, 'd'), (2, 'e'), (2, 'f')]);
+    }
+
+    #[test]
+    fn skip_children() {
+        let mut root = tree();
+        let mut nodes = dfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((_, node)) = nodes.next() {
+            let name = node.name;
+            seen.push(name);
+            if name == 'b' {
+                nodes.skip_children();
+            }
+        }
+        assert_eq!(seen, ['a', 'b', 'c', 'f']);
+
+        let mut nodes = dfs_mut(&mut root, children).post_order();
+        let mut seen = Vec::new();
+        while let Some((_, node)) = nodes.next() {
+            let name = node.name;
+            seen.push(name);
+            nodes.skip_children();
+        }
+        assert_eq!(seen, ['d', 'e', 'b', 'f', 'c', 'a']);
+
+        let mut nodes = bfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((_, node)) = nodes.next() {
+            let name = node.name;
+            seen.push(name);
+            if name == 'b' {
+                nodes.skip_children();
+            }
+        }
+        assert_eq!(seen, ['a', 'b', 'c', 'f']);
+    }
+
+    #[test]
+    fn children_changed_before_descending() {
+        let mut root = tree();
+        let mut nodes = dfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((depth, node)) = nodes.next() {
+            match node.name {
+                'b' => node.children.clear(),
+                'c' => node.children.push(new_node('g', vec![new_node('h', vec![])])),
+                _ => {}
+            }
+            seen.push((depth, node.name));
+        }
+        assert_eq!(seen, [(0, 'a'), (1, 'b'), (1, 'c'), (2, 'f'), (2, 'g'), (3, 'h')]);
+
+        let mut root = tree();
+        let mut nodes = bfs_mut(&mut root, children);
+        let mut seen = Vec::new();
+        while let Some((depth, node)) = nodes.next() {
+            if node.name == 'a' {
+                node.children.swap(0, 1);
+                node.children.push(new_node('g', vec![]));
+            }
+            seen.push((depth, node.name));
+        }
+        assert_eq!(seen, [(0, 'a'), (1, 'c'), (1, 'b'), (1, 'g'), (2, 'f'), (2, 'd'), (2, 'e')]);
+    }
+}
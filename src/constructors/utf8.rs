@@ -0,0 +1,533 @@
+use alloc::string::String;
+use core::{
+    char::REPLACEMENT_CHARACTER,
+    fmt,
+    marker::PhantomData,
+    str::{from_utf8, from_utf8_unchecked},
+};
+#[cfg(feature = "std")]
+use std::{
+    io::{self, ErrorKind, Read},
+    vec,
+    vec::Vec,
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+use crate::{
+    fallible::IntoResult,
+    hkt::{extend_lifetime, HKT},
+    lending_iter::LendedItem,
+};
+
+/// Invalid UTF-8 found by [`Utf8Chunks`], [`TryUtf8Chunks`] or [`ReadUtf8`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8Error {
+    position: u64,
+    incomplete: bool,
+}
+
+impl Utf8Error {
+    /// Offset in the input of the first byte of the invalid sequence.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Whether the input ended in the middle of a code point.
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.incomplete {
+            true => write!(f, "incomplete UTF-8 sequence at byte {}", self.position),
+            false => write!(f, "invalid UTF-8 sequence at byte {}", self.position),
+        }
+    }
+}
+
+impl core::error::Error for Utf8Error {}
+
+#[cfg(feature = "std")]
+impl From<Utf8Error> for io::Error {
+    fn from(e: Utf8Error) -> Self {
+        io::Error::new(ErrorKind::InvalidData, e)
+    }
+}
+
+/// Decoding state carried from one chunk to the next.
+struct Decoder {
+    /// Start of a code point split across chunks.
+    carry: [u8; 4],
+    carry_len: usize,
+    /// Output for chunks that cannot be lent in place.
+    buf: String,
+    position: u64,
+    lossy: bool,
+    failed: bool,
+    /// Error to lend after the valid part of the chunk it was found in.
+    pending: Option<Utf8Error>,
+}
+
+impl Decoder {
+    fn new() -> Self {
+        Decoder {
+            carry: [0; 4],
+            carry_len: 0,
+            buf: String::new(),
+            position: 0,
+            lossy: false,
+            failed: false,
+            pending: None,
+        }
+    }
+
+    fn error(&mut self, position: u64, incomplete: bool) -> Utf8Error {
+        self.failed = true;
+        Utf8Error {
+            position,
+            incomplete,
+        }
+    }
+
+    /// Decodes the next chunk, returning `None` if it holds no complete code point.
+    ///
+    /// The chunk is lent back in place unless it has to be joined with the end of the
+    /// previous one or invalid sequences have to be replaced.
+    fn decode<'a>(&'a mut self, chunk: &'a [u8]) -> Result<Option<&'a str>, Utf8Error> {
+        self.position += chunk.len() as u64;
+        self.buf.clear();
+        let mut rest = chunk;
+        while self.carry_len > 0 {
+            let Some((&byte, tail)) = rest.split_first() else {
+                return Ok(None);
+            };
+            self.carry[self.carry_len] = byte;
+            match from_utf8(&self.carry[..=self.carry_len]) {
+                Ok(c) => {
+                    self.buf.push_str(c);
+                    self.carry_len = 0;
+                }
+                Err(e) if e.error_len().is_none() => self.carry_len += 1,
+                Err(_) => {
+                    // The byte does not continue the code point, so it is decoded again on
+                    // its own
+                    let position = self.position - (rest.len() + self.carry_len) as u64;
+                    self.carry_len = 0;
+                    if !self.lossy {
+                        return Err(self.error(position, false));
+                    }
+                    self.buf.push(REPLACEMENT_CHARACTER);
+                    continue;
+                }
+            }
+            rest = tail;
+        }
+        loop {
+            let e = match from_utf8(rest) {
+                Ok(valid) => return Ok(self.output(valid)),
+                Err(e) => e,
+            };
+            let (valid, after) = rest.split_at(e.valid_up_to());
+            // SAFETY:
+            // Bytes up to `valid_up_to` are valid UTF-8
+            let valid = unsafe { from_utf8_unchecked(valid) };
+            let Some(error_len) = e.error_len() else {
+                self.carry[..after.len()].copy_from_slice(after);
+                self.carry_len = after.len();
+                return Ok(self.output(valid));
+            };
+            if !self.lossy {
+                // Whatever is valid before the error is still lent
+                let error = self.error(self.position - after.len() as u64, false);
+                if valid.is_empty() && self.buf.is_empty() {
+                    return Err(error);
+                }
+                self.pending = Some(error);
+                return Ok(self.output(valid));
+            }
+            self.buf.push_str(valid);
+            self.buf.push(REPLACEMENT_CHARACTER);
+            rest = &after[error_len..];
+        }
+    }
+
+    /// Same as [`decode`](Decoder::decode), but always lends from the output buffer so that
+    /// the chunk does not need to outlive the decoded text.
+    fn decode_owned(&mut self, chunk: &[u8]) -> Result<Option<&str>, Utf8Error> {
+        let Some(len) = self.decode(chunk)?.map(str::len) else {
+            return Ok(None);
+        };
+        if self.buf.is_empty() {
+            // SAFETY:
+            // Text is only lent in place when nothing precedes it in the output, in which
+            // case it is the valid start of the chunk
+            self.buf.push_str(unsafe { from_utf8_unchecked(&chunk[..len]) });
+        }
+        Ok(Some(&self.buf))
+    }
+
+    /// Lends `valid` in place if nothing was written to the output yet.
+    fn output<'a>(&'a mut self, valid: &'a str) -> Option<&'a str> {
+        if self.buf.is_empty() {
+            return (!valid.is_empty()).then_some(valid);
+        }
+        self.buf.push_str(valid);
+        Some(&self.buf)
+    }
+
+    /// Handles the end of the input, which must not be in the middle of a code point.
+    fn finish(&mut self) -> Option<Result<&str, Utf8Error>> {
+        if self.carry_len == 0 {
+            return None;
+        }
+        let position = self.position - self.carry_len as u64;
+        self.carry_len = 0;
+        if !self.lossy {
+            return Some(Err(self.error(position, true)));
+        }
+        self.buf.clear();
+        self.buf.push(REPLACEMENT_CHARACTER);
+        Some(Ok(&self.buf))
+    }
+}
+
+/// Lends the contents of a lending iterator of byte chunks as string slices, carrying code
+/// points split across chunks over to the next one.
+///
+/// Created by [`utf8_chunks`].
+pub struct Utf8Chunks<I> {
+    iter: I,
+    decoder: Decoder,
+}
+
+impl<I> Utf8Chunks<I> {
+    /// Replaces invalid sequences with U+FFFD instead of failing on them.
+    pub fn lossy(mut self) -> Self {
+        self.decoder.lossy = true;
+        self
+    }
+}
+
+#[gat]
+impl<I> LendingIter for Utf8Chunks<I>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: AsRef<[u8]>,
+{
+    type Item<'a> = Result<&'a str, Utf8Error>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if let Some(error) = self.decoder.pending.take() {
+            return Some(Err(error));
+        }
+        if self.decoder.failed {
+            return None;
+        }
+        while let Some(chunk) = self.iter.next() {
+            // SAFETY:
+            // The decoded chunk is either dropped or returned by the end of the loop
+            // (polonius loop pattern)
+            let decoded = unsafe {
+                extend_lifetime::<HKT!(Result<Option<&str>, Utf8Error>)>(
+                    self.decoder.decode_owned(chunk.as_ref()),
+                )
+            };
+            if let Some(decoded) = decoded.transpose() {
+                return Some(decoded);
+            }
+        }
+        self.decoder.finish()
+    }
+}
+
+/// Lends the contents of a lending iterator of fallible byte chunks as string slices,
+/// carrying code points split across chunks over to the next one.
+///
+/// Created by [`try_utf8_chunks`].
+pub struct TryUtf8Chunks<I, E> {
+    iter: I,
+    decoder: Decoder,
+    phantom: PhantomData<fn() -> E>,
+}
+
+impl<I, E> TryUtf8Chunks<I, E> {
+    /// Replaces invalid sequences with U+FFFD instead of failing on them.
+    pub fn lossy(mut self) -> Self {
+        self.decoder.lossy = true;
+        self
+    }
+}
+
+#[gat]
+impl<I, E> LendingIter for TryUtf8Chunks<I, E>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult<Err = E>,
+    for<'b> <LendedItem<'b, I> as IntoResult>::Ok: AsRef<[u8]>,
+    E: From<Utf8Error>,
+{
+    type Item<'a> = Result<&'a str, E>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if let Some(error) = self.decoder.pending.take() {
+            return Some(Err(error.into()));
+        }
+        if self.decoder.failed {
+            return None;
+        }
+        while let Some(chunk) = self.iter.next() {
+            let chunk = match chunk.into_result() {
+                Ok(chunk) => chunk,
+                Err(e) => return Some(Err(e)),
+            };
+            // SAFETY:
+            // The decoded chunk is either dropped or returned by the end of the loop
+            // (polonius loop pattern)
+            let decoded = unsafe {
+                extend_lifetime::<HKT!(Result<Option<&str>, Utf8Error>)>(
+                    self.decoder.decode_owned(chunk.as_ref()),
+                )
+            };
+            if let Some(decoded) = decoded.transpose() {
+                return Some(decoded.map_err(Into::into));
+            }
+        }
+        self.decoder.finish().map(|r| r.map_err(Into::into))
+    }
+}
+
+/// Lends the contents of a reader as string slices, carrying code points split across
+/// reads over to the next one.
+///
+/// Created by [`read_utf8`].
+#[cfg(feature = "std")]
+pub struct ReadUtf8<R> {
+    reader: R,
+    read_buf: Vec<u8>,
+    decoder: Decoder,
+}
+
+#[cfg(feature = "std")]
+impl<R> ReadUtf8<R> {
+    /// Replaces invalid sequences with U+FFFD instead of failing on them.
+    pub fn lossy(mut self) -> Self {
+        self.decoder.lossy = true;
+        self
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+#[gat]
+impl<R: Read> LendingIter for ReadUtf8<R> {
+    type Item<'a> = io::Result<&'a str>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if let Some(error) = self.decoder.pending.take() {
+            return Some(Err(error.into()));
+        }
+        if self.decoder.failed {
+            return None;
+        }
+        loop {
+            let len = match self.reader.read(&mut self.read_buf) {
+                Ok(0) => return self.decoder.finish().map(|r| r.map_err(Into::into)),
+                Ok(len) => len,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            };
+            // SAFETY:
+            // The decoded chunk is either dropped or returned by the end of the loop
+            // (polonius loop pattern)
+            let decoded = unsafe {
+                extend_lifetime::<HKT!(Result<Option<&str>, Utf8Error>)>(
+                    self.decoder.decode(&self.read_buf[..len]),
+                )
+            };
+            if let Some(decoded) = decoded.transpose() {
+                return Some(decoded.map_err(Into::into));
+            }
+        }
+    }
+}
+
+/// Lends the byte chunks lent by `iter` decoded as UTF-8.
+///
+/// Chunks may be anything holding bytes, such as slices or vectors, and their text is
+/// decoded into a reused buffer. Chunks holding no complete code point are skipped.
+/// Invalid UTF-8, including input ending in the middle of a code point, is lent as an
+/// error right after the valid text preceding it, and ends the iteration, unless
+/// [`lossy`](Utf8Chunks::lossy) is used.
+///
+/// Use [`try_utf8_chunks`] for chunks that may fail, such as the ones lent by
+/// [`read_chunks`](super::read::read_chunks).
+pub fn utf8_chunks<I>(iter: I) -> Utf8Chunks<I>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: AsRef<[u8]>,
+{
+    Utf8Chunks {
+        iter,
+        decoder: Decoder::new(),
+    }
+}
+
+/// Lends the `Ok` byte chunks lent by `iter` decoded as UTF-8, along with their errors.
+///
+/// Behaves like [`utf8_chunks`], with invalid UTF-8 converted into the error type of the
+/// chunks. Errors of the chunks are lent as they come, and decoding resumes after them.
+///
+/// With the `std` feature, this decodes the chunks read by
+/// [`read_chunks`](super::read::read_chunks), lending `io::Result<&str>`.
+///
+/// ```
+/// use lending_iter::{constructors::utf8::Utf8Error, prelude::*};
+///
+/// #[derive(Debug)]
+/// enum Error {
+///     Disconnected,
+///     Utf8(Utf8Error),
+/// }
+///
+/// impl From<Utf8Error> for Error {
+///     fn from(e: Utf8Error) -> Self {
+///         Error::Utf8(e)
+///     }
+/// }
+///
+/// let chunks: Vec<Result<&[u8], Error>> =
+///     vec![Ok(b"a\xc3"), Err(Error::Disconnected), Ok(b"\xb1b")];
+/// let mut text = try_utf8_chunks(chunks.into_iter().lending());
+/// assert_eq!(text.next().unwrap().unwrap(), "a");
+/// assert!(matches!(text.next(), Some(Err(Error::Disconnected))));
+/// assert_eq!(text.next().unwrap().unwrap(), "ñb");
+/// assert!(text.next().is_none());
+/// ```
+pub fn try_utf8_chunks<I, E>(iter: I) -> TryUtf8Chunks<I, E>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: IntoResult<Err = E>,
+    for<'a> <LendedItem<'a, I> as IntoResult>::Ok: AsRef<[u8]>,
+    E: From<Utf8Error>,
+{
+    TryUtf8Chunks {
+        iter,
+        decoder: Decoder::new(),
+        phantom: PhantomData,
+    }
+}
+
+/// Lends the contents of `reader` decoded as UTF-8, reading `buf_size` bytes at a time.
+///
+/// Behaves like [`utf8_chunks`], with invalid UTF-8 lent as an error of kind
+/// [`ErrorKind::InvalidData`]. Reads interrupted by [`ErrorKind::Interrupted`] are retried,
+/// other errors are lent.
+///
+/// # Panics
+/// Panics if `buf_size` is zero.
+#[cfg(feature = "std")]
+pub fn read_utf8<R: Read>(reader: R, buf_size: usize) -> ReadUtf8<R> {
+    assert!(buf_size > 0, "buffer size must be non-zero");
+    ReadUtf8 {
+        reader,
+        read_buf: vec![0; buf_size],
+        decoder: Decoder::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+    use nougat::{apply, Gat};
+
+    use super::Utf8Error;
+    use crate::prelude::*;
+
+    #[apply(Gat!)]
+    fn collect<I>(mut iter: I) -> Vec<Result<String, Utf8Error>>
+    where
+        I: for<'a> LendingIter<Item<'a> = Result<&'a str, Utf8Error>>,
+    {
+        let mut out = Vec::new();
+        while let Some(text) = iter.next() {
+            out.push(text.map(ToOwned::to_owned));
+        }
+        out
+    }
+
+    #[test]
+    fn slices_split_inside_code_points() {
+        let bytes = "añ€b".as_bytes();
+        let chunks: Vec<&[u8]> = bytes.chunks(1).collect();
+        let text: Vec<_> = collect(utf8_chunks(chunks.into_iter().lending()));
+        let text: String = text.into_iter().map(Result::unwrap).collect();
+        assert_eq!(text, "añ€b");
+    }
+
+    #[test]
+    fn owned_chunks() {
+        let chunks = vec![b"ab\xc3".to_vec(), b"\xb1c".to_vec()];
+        let text = collect(utf8_chunks(chunks.into_iter().lending()));
+        assert_eq!(text, [Ok("ab".into()), Ok("ñc".into())]);
+    }
+
+    #[test]
+    fn lossy_chunks() {
+        let chunks: Vec<&[u8]> = vec![b"a\xff", b"b\xc3"];
+        let text = collect(utf8_chunks(chunks.into_iter().lending()).lossy());
+        let text: String = text.into_iter().map(Result::unwrap).collect();
+        assert_eq!(text, "a\u{fffd}b\u{fffd}");
+    }
+
+    #[test]
+    fn invalid_chunks_end_iteration() {
+        let chunks: Vec<&[u8]> = vec![b"a\xff", b"b"];
+        let mut text = utf8_chunks(chunks.into_iter().lending());
+        assert_eq!(text.next().map(|t| t.map(ToOwned::to_owned)), Some(Ok("a".into())));
+        assert!(matches!(text.next(), Some(Err(_))));
+        assert!(text.next().is_none());
+    }
+
+    #[test]
+    fn fallible_chunks_pass_errors_through() {
+        let chunks: Vec<Result<&[u8], Utf8Error>> = vec![
+            Ok(b"a\xc3"),
+            Err(Utf8Error {
+                position: 0,
+                incomplete: false,
+            }),
+            Ok(b"\xb1"),
+        ];
+        let mut text = try_utf8_chunks(chunks.into_iter().lending());
+        assert_eq!(text.next().map(|t| t.map(ToOwned::to_owned)), Some(Ok("a".into())));
+        assert!(matches!(text.next(), Some(Err(_))));
+        assert_eq!(text.next().map(|t| t.map(ToOwned::to_owned)), Some(Ok("ñ".into())));
+        assert!(text.next().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_chunks_source() {
+        let mut text = try_utf8_chunks(read_chunks("añ€b".as_bytes(), 1));
+        let mut decoded = String::new();
+        while let Some(chunk) = text.next() {
+            decoded.push_str(chunk.unwrap());
+        }
+        assert_eq!(decoded, "añ€b");
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fs::{self, FileType, Metadata, ReadDir},
+    io,
+    path::{Path, PathBuf},
+    vec::{self, Vec},
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Options of [`walk_dir`].
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    max_depth: Option<usize>,
+    follow_links: bool,
+    sort: bool,
+}
+
+impl WalkOptions {
+    /// Does not descend into directories deeper than `max_depth`, the root being at depth 0.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Follows symbolic links to directories, failing on links back to a directory being
+    /// walked. Links are not followed by default.
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Lends the entries of every directory sorted by file name, which requires reading
+    /// them all before lending the first one.
+    pub fn sort_by_file_name(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+}
+
+/// Entry lent by [`WalkDir`], borrowing its path from the walker.
+#[derive(Debug, Clone, Copy)]
+pub struct DirEntryRef<'a> {
+    path: &'a Path,
+    depth: usize,
+    file_type: FileType,
+    follow_links: bool,
+}
+
+impl<'a> DirEntryRef<'a> {
+    /// Path of the entry, starting with the root of the walk.
+    pub fn path(&self) -> &'a Path {
+        self.path
+    }
+
+    pub fn file_name(&self) -> &'a OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Depth of the entry, the root being at depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// File type of the entry, which is that of the target of a symbolic link when links
+    /// are followed.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Queries the metadata of the entry, following symbolic links if links are followed.
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        match self.follow_links {
+            true => fs::metadata(self.path),
+            false => fs::symlink_metadata(self.path),
+        }
+    }
+}
+
+enum Entries {
+    Unsorted(ReadDir),
+    Sorted(vec::IntoIter<io::Result<(OsString, FileType)>>),
+}
+
+impl Iterator for Entries {
+    type Item = io::Result<(OsString, FileType)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Entries::Unsorted(read_dir) => {
+                let entry = read_dir.next()?;
+                Some(entry.and_then(|entry| Ok((entry.file_name(), entry.file_type()?))))
+            }
+            Entries::Sorted(entries) => entries.next(),
+        }
+    }
+}
+
+/// Directory being walked.
+struct Frame {
+    entries: Entries,
+    /// Depth of its entries.
+    depth: usize,
+    /// Canonical path, to detect loops when following links.
+    canonical: Option<PathBuf>,
+}
+
+/// Lends every entry of a directory tree in depth-first order, keeping the path of the
+/// current entry in a single buffer.
+///
+/// Created by [`walk_dir`].
+pub struct WalkDir {
+    options: WalkOptions,
+    path: PathBuf,
+    stack: Vec<Frame>,
+    /// Whether the root is still to be lent.
+    root: bool,
+    /// Whether the last lent entry is a directory to descend into.
+    descend: bool,
+    /// Whether `path` ends with the name of the last lent entry.
+    entry: bool,
+}
+
+impl WalkDir {
+    /// Does not descend into the last lent entry if it is a directory, and otherwise skips
+    /// the remaining entries of the directory containing it.
+    pub fn skip_current_dir(&mut self) {
+        if self.descend {
+            self.descend = false;
+            return;
+        }
+        if self.stack.pop().is_none() {
+            return;
+        }
+        if self.entry {
+            self.entry = false;
+            self.path.pop();
+        }
+        if !self.stack.is_empty() {
+            self.path.pop();
+        }
+    }
+
+    fn file_type(&self, file_type: FileType) -> FileType {
+        if !(self.options.follow_links && file_type.is_symlink()) {
+            return file_type;
+        }
+        // Broken links are lent as links
+        fs::metadata(&self.path).map_or(file_type, |metadata| metadata.file_type())
+    }
+
+    fn entry(&mut self, file_type: FileType, depth: usize) -> DirEntryRef<'_> {
+        self.descend = file_type.is_dir() && self.options.max_depth.is_none_or(|max| depth < max);
+        DirEntryRef {
+            path: &self.path,
+            depth,
+            file_type,
+            follow_links: self.options.follow_links,
+        }
+    }
+
+    /// Starts walking the directory of the last lent entry.
+    fn open(&mut self) -> io::Result<()> {
+        let canonical = match self.options.follow_links {
+            true => {
+                let canonical = fs::canonicalize(&self.path)?;
+                if self.stack.iter().any(|frame| frame.canonical.as_ref() == Some(&canonical)) {
+                    let message = std::format!("filesystem loop at {}", self.path.display());
+                    return Err(io::Error::other(message));
+                }
+                Some(canonical)
+            }
+            false => None,
+        };
+        let read_dir = fs::read_dir(&self.path)?;
+        let entries = match self.options.sort {
+            true => {
+                let mut entries: Vec<_> = Entries::Unsorted(read_dir).collect();
+                entries.sort_by(|a, b| match (a, b) {
+                    (Ok((a, _)), Ok((b, _))) => a.cmp(b),
+                    (a, b) => b.is_ok().cmp(&a.is_ok()),
+                });
+                Entries::Sorted(entries.into_iter())
+            }
+            false => Entries::Unsorted(read_dir),
+        };
+        self.stack.push(Frame {
+            entries,
+            depth: self.stack.len() + 1,
+            canonical,
+        });
+        Ok(())
+    }
+}
+
+#[gat]
+impl LendingIter for WalkDir {
+    type Item<'a> = io::Result<DirEntryRef<'a>>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.root {
+            self.root = false;
+            let metadata = match self.options.follow_links {
+                true => fs::metadata(&self.path),
+                false => fs::symlink_metadata(&self.path),
+            };
+            return Some(metadata.map(|metadata| self.entry(metadata.file_type(), 0)));
+        }
+        if self.descend {
+            self.descend = false;
+            self.entry = false;
+            if let Err(e) = self.open() {
+                if !self.stack.is_empty() {
+                    self.path.pop();
+                }
+                return Some(Err(e));
+            }
+        } else if self.entry {
+            self.entry = false;
+            self.path.pop();
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let depth = frame.depth;
+            match frame.entries.next() {
+                Some(Ok((name, file_type))) => {
+                    self.path.push(name);
+                    self.entry = true;
+                    let file_type = self.file_type(file_type);
+                    return Some(Ok(self.entry(file_type, depth)));
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.stack.pop();
+                    if !self.stack.is_empty() {
+                        self.path.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Lends `root` and every entry below it, each directory being followed by its contents.
+///
+/// Errors reading a directory or one of its entries are lent, and the walk goes on with the
+/// next entry.
+pub fn walk_dir(root: impl AsRef<Path>, options: WalkOptions) -> WalkDir {
+    WalkDir {
+        options,
+        path: root.as_ref().to_path_buf(),
+        stack: Vec::new(),
+        root: true,
+        descend: false,
+        entry: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+        process,
+        string::String,
+        vec::Vec,
+    };
+
+    use super::{walk_dir, WalkDir, WalkOptions};
+    use crate::prelude::LendingIter;
+
+    /// Temporary directory holding `a/{x.txt, y/z.txt}`, `b.txt` and `c/w.txt`, removed
+    /// when dropped.
+    struct Tree(PathBuf);
+
+    impl Tree {
+        fn new(name: &str) -> Self {
+            let root = env::temp_dir().join(format!("lending-iter-{}-{name}", process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("a/y")).unwrap();
+            fs::create_dir(root.join("c")).unwrap();
+            for file in ["a/x.txt", "a/y/z.txt", "b.txt", "c/w.txt"] {
+                fs::write(root.join(file), file).unwrap();
+            }
+            Tree(root)
+        }
+    }
+
+    impl Drop for Tree {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Walks `root`, calling `f` with the walker and the relative path of every entry, and
+    /// returns the depth and relative path of every entry, or `None` for errors.
+    fn walk(
+        root: &Path,
+        options: WalkOptions,
+        mut f: impl FnMut(&mut WalkDir, &str),
+    ) -> Vec<Option<(usize, String)>> {
+        let mut walker = walk_dir(root, options);
+        let mut seen = Vec::new();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                seen.push(None);
+                continue;
+            };
+            let path = entry.path().strip_prefix(root).unwrap();
+            let path = path.to_str().unwrap().replace('\\', "/");
+            let depth = entry.depth();
+            assert_eq!(depth, path.split('/').filter(|name| !name.is_empty()).count());
+            f(&mut walker, &path);
+            seen.push(Some((depth, path)));
+        }
+        seen
+    }
+
+    fn sorted() -> WalkOptions {
+        WalkOptions::default().sort_by_file_name(true)
+    }
+
+    fn paths(seen: &[Option<(usize, String)>]) -> Vec<&str> {
+        seen.iter()
+            .map(|entry| entry.as_ref().map_or("error", |(_, path)| path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn sorted_walk() {
+        let tree = Tree::new("sorted");
+        let seen = walk(&tree.0, sorted(), |_, _| {});
+        let expected = ["", "a", "a/x.txt", "a/y", "a/y/z.txt", "b.txt", "c", "c/w.txt"];
+        assert_eq!(paths(&seen), expected);
+    }
+
+    #[test]
+    fn unsorted_walk() {
+        let tree = Tree::new("unsorted");
+        let seen = walk(&tree.0, WalkOptions::default(), |_, _| {});
+        let mut seen = paths(&seen);
+        seen.sort();
+        let expected = ["", "a", "a/x.txt", "a/y", "a/y/z.txt", "b.txt", "c", "c/w.txt"];
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn max_depth() {
+        let tree = Tree::new("max-depth");
+        let seen = walk(&tree.0, sorted().max_depth(1), |_, _| {});
+        assert_eq!(paths(&seen), ["", "a", "b.txt", "c"]);
+        let seen = walk(&tree.0, sorted().max_depth(2), |_, _| {});
+        assert_eq!(paths(&seen), ["", "a", "a/x.txt", "a/y", "b.txt", "c", "c/w.txt"]);
+        let seen = walk(&tree.0, sorted().max_depth(0), |_, _| {});
+        assert_eq!(paths(&seen), [""]);
+    }
+
+    #[test]
+    fn skip_current_dir_of_directory() {
+        let tree = Tree::new("skip-dir");
+        let seen = walk(&tree.0, sorted(), |walker, path| {
+            if path == "a" {
+                walker.skip_current_dir();
+            }
+        });
+        assert_eq!(paths(&seen), ["", "a", "b.txt", "c", "c/w.txt"]);
+        let seen = walk(&tree.0, sorted(), |walker, path| {
+            if path.is_empty() {
+                walker.skip_current_dir();
+            }
+        });
+        assert_eq!(paths(&seen), [""]);
+    }
+
+    #[test]
+    fn skip_current_dir_of_file() {
+        let tree = Tree::new("skip-file");
+        let seen = walk(&tree.0, sorted(), |walker, path| {
+            if path == "a/x.txt" {
+                walker.skip_current_dir();
+            }
+        });
+        assert_eq!(paths(&seen), ["", "a", "a/x.txt", "b.txt", "c", "c/w.txt"]);
+        let seen = walk(&tree.0, sorted(), |walker, path| {
+            if path == "a/y/z.txt" || path == "b.txt" {
+                walker.skip_current_dir();
+            }
+        });
+        assert_eq!(paths(&seen), ["", "a", "a/x.txt", "a/y", "a/y/z.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn unreadable_directory() {
+        let tree = Tree::new("unreadable");
+        // The directory cannot be read once it is gone, whatever the permissions
+        let seen = walk(&tree.0, sorted(), |_, path| {
+            if path == "a" {
+                fs::remove_dir_all(tree.0.join("a")).unwrap();
+            }
+        });
+        assert_eq!(paths(&seen), ["", "a", "error", "b.txt", "c", "c/w.txt"]);
+    }
+
+    #[test]
+    fn missing_root() {
+        let tree = Tree::new("missing-root");
+        let seen = walk(&tree.0.join("missing"), sorted(), |_, _| {});
+        assert_eq!(seen, [None]);
+    }
+}
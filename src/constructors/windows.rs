@@ -0,0 +1,191 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Lends every window of `n` consecutive items of an iterator.
+///
+/// Items are kept twice in a buffer of length `2 * n`, at positions `i` and `i + n`, so
+/// that the last `n` items are always contiguous. Each item is thus cloned once, however
+/// many windows it belongs to.
+///
+/// Created by [`IntoLending::windows_lending`](super::from_iter::IntoLending::windows_lending).
+#[cfg(feature = "alloc")]
+pub struct WindowsLending<I: Iterator> {
+    pub(crate) iter: I,
+    pub(crate) n: usize,
+    pub(crate) buf: Vec<I::Item>,
+    pub(crate) head: usize,
+}
+
+#[cfg(feature = "alloc")]
+#[gat]
+impl<I> LendingIter for WindowsLending<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item<'a> = &'a [I::Item]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let n = self.n;
+        if self.buf.len() < 2 * n {
+            if !self.buf.is_empty() {
+                return None;
+            }
+            self.buf.extend(self.iter.by_ref().take(n));
+            if self.buf.len() < n {
+                return None;
+            }
+            self.buf.extend_from_within(..);
+            return Some(&self.buf[..n]);
+        }
+        let item = self.iter.next()?;
+        self.buf[self.head] = item.clone();
+        self.buf[self.head + n] = item;
+        self.head = (self.head + 1) % n;
+        Some(&self.buf[self.head..self.head + n])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.buf.len() {
+            0 => window_count(self.iter.size_hint(), self.n),
+            len if len < 2 * self.n => (0, Some(0)),
+            _ => self.iter.size_hint(),
+        }
+    }
+}
+
+/// Lends every window of `N` consecutive items of an iterator, without allocating.
+///
+/// Created by
+/// [`IntoLending::array_windows_lending`](super::from_iter::IntoLending::array_windows_lending).
+pub struct ArrayWindowsLending<I: Iterator, const N: usize> {
+    pub(crate) iter: I,
+    pub(crate) window: ArrayWindowBuf<I::Item, N>,
+}
+
+#[gat]
+impl<I, const N: usize> LendingIter for ArrayWindowsLending<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item<'a> = &'a [I::Item; N]
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let iter = &mut self.iter;
+        self.window.advance(|| iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.window.size_hint(self.iter.size_hint())
+    }
+}
+
+/// Last `N` items of an iterator, shared by the lenders of fixed size windows.
+///
+/// Every item is written at `head` in both halves of the buffer, so that the window
+/// starting right after it is contiguous.
+pub(crate) struct ArrayWindowBuf<T, const N: usize> {
+    buf: Option<[[T; N]; 2]>,
+    head: usize,
+    done: bool,
+}
+
+impl<T, const N: usize> ArrayWindowBuf<T, N> {
+    pub(crate) fn new() -> Self {
+        ArrayWindowBuf {
+            buf: None,
+            head: 0,
+            done: false,
+        }
+    }
+
+    /// Number of windows left, given the size hint of the items left.
+    pub(crate) fn size_hint(&self, items: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        match (self.done, &self.buf) {
+            (true, _) => (0, Some(0)),
+            (false, None) => window_count(items, N),
+            (false, Some(_)) => items,
+        }
+    }
+}
+
+impl<T: Clone, const N: usize> ArrayWindowBuf<T, N> {
+    /// Moves to the next window, taking its first `N` items from `next`, then one item at a
+    /// time.
+    pub(crate) fn advance(&mut self, mut next: impl FnMut() -> Option<T>) -> Option<&[T; N]> {
+        if self.done {
+            return None;
+        }
+        if self.buf.is_none() {
+            let items = [(); N].map(|()| next());
+            if items.iter().any(Option::is_none) {
+                self.done = true;
+                return None;
+            }
+            let items = items.map(Option::unwrap);
+            let buf = self.buf.insert([items.clone(), items]);
+            return Some(&buf[0]);
+        }
+        let Some(item) = next() else {
+            self.done = true;
+            return None;
+        };
+        let buf = self.buf.as_mut()?.as_flattened_mut();
+        buf[self.head] = item.clone();
+        buf[self.head + N] = item;
+        self.head = (self.head + 1) % N;
+        buf[self.head..self.head + N].try_into().ok()
+    }
+}
+
+/// Number of windows of `n` items over the items of an iterator with the given size hint.
+fn window_count((lower, upper): (usize, Option<usize>), n: usize) -> (usize, Option<usize>) {
+    (
+        lower.saturating_sub(n - 1),
+        upper.map(|upper| upper.saturating_sub(n - 1)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::{IntoLending, LendingIter};
+
+    #[test]
+    fn size_hint_of_unbounded_source() {
+        let windows = (0u64..).array_windows_lending::<2>();
+        assert_eq!(windows.size_hint(), (usize::MAX - 1, None));
+        #[cfg(feature = "alloc")]
+        assert_eq!((0u64..).windows_lending(2).size_hint(), (usize::MAX - 1, None));
+    }
+
+    #[test]
+    fn array_windows_agree_with_map_windows() {
+        let mut windows = (1..6).array_windows_lending::<3>();
+        let mut sums = (1..6).lending().map_windows::<3, _>(|w: &[i32; 3]| w.iter().sum());
+        loop {
+            assert_eq!(sums.size_hint(), windows.size_hint());
+            let Some(window) = windows.next() else { break };
+            assert_eq!(sums.next(), Some(window.iter().sum::<i32>()));
+        }
+        assert_eq!(sums.next(), None);
+        assert_eq!((windows.next(), sums.next()), (None, None));
+        assert_eq!(windows.size_hint(), (0, Some(0)));
+        assert_eq!(sums.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn size_hint_of_short_source() {
+        assert_eq!([1, 2, 3].into_iter().array_windows_lending::<2>().size_hint(), (2, Some(2)));
+        assert_eq!([1].into_iter().array_windows_lending::<2>().size_hint(), (0, Some(0)));
+    }
+}
@@ -0,0 +1,170 @@
+use core::ops::{Index, IndexMut};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
+/// Mutable view of a rectangular region of a row-major buffer.
+pub struct StridedMut<'a, T> {
+    data: &'a mut [T],
+    stride: usize,
+    height: usize,
+    width: usize,
+    origin: (usize, usize),
+}
+
+impl<'a, T> StridedMut<'a, T> {
+    /// Position `(y, x)` of the top left element of the view in the buffer.
+    pub fn origin(&self) -> (usize, usize) {
+        self.origin
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the `i`-th row of the view.
+    ///
+    /// # Panics
+    /// Panics if `i` is not less than the height of the view.
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.height, "row {i} out of range for height {}", self.height);
+        &self.data[i * self.stride..][..self.width]
+    }
+
+    /// Returns the `i`-th row of the view.
+    ///
+    /// # Panics
+    /// Panics if `i` is not less than the height of the view.
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        assert!(i < self.height, "row {i} out of range for height {}", self.height);
+        &mut self.data[i * self.stride..][..self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        let width = self.width;
+        self.data.chunks(self.stride).map(move |row| &row[..width])
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        let width = self.width;
+        self.data.chunks_mut(self.stride).map(move |row| &mut row[..width])
+    }
+
+    fn offset(&self, (y, x): (usize, usize)) -> usize {
+        assert!(
+            y < self.height && x < self.width,
+            "index {:?} out of range for {}x{} view",
+            (y, x),
+            self.height,
+            self.width,
+        );
+        y * self.stride + x
+    }
+}
+
+impl<T> Index<(usize, usize)> for StridedMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &T {
+        &self.data[self.offset(index)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for StridedMut<'_, T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+        let offset = self.offset(index);
+        &mut self.data[offset]
+    }
+}
+
+/// Lends mutable views of rectangular regions of a row-major buffer, moving along rows
+/// first.
+///
+/// Created by [`windows2d_mut`] and [`tiles_mut`].
+pub struct Windows2dMut<'s, T> {
+    buf: &'s mut [T],
+    stride: usize,
+    size: (usize, usize),
+    step: (usize, usize),
+    next: (usize, usize),
+}
+
+#[gat]
+impl<'s, T> LendingIter for Windows2dMut<'s, T> {
+    type Item<'a> = StridedMut<'a, T>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        let ((height, width), (y, x)) = (self.size, self.next);
+        if y + height > self.buf.len() / self.stride || width > self.stride {
+            return None;
+        }
+        self.next.1 += self.step.1;
+        if self.next.1 + width > self.stride {
+            self.next = (y + self.step.0, 0);
+        }
+        let start = y * self.stride + x;
+        Some(StridedMut {
+            data: &mut self.buf[start..start + (height - 1) * self.stride + width],
+            stride: self.stride,
+            height,
+            width,
+            origin: (y, x),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let ((height, width), (y, x)) = (self.size, self.next);
+        let rows = (self.buf.len() / self.stride + self.step.0).saturating_sub(y + height);
+        let columns = (self.stride + self.step.1).saturating_sub(width);
+        let remaining =
+            (rows / self.step.0 * (columns / self.step.1)).saturating_sub(x / self.step.1);
+        (remaining, Some(remaining))
+    }
+}
+
+fn windows2d<T>(
+    buf: &mut [T],
+    width: usize,
+    size: (usize, usize),
+    step: (usize, usize),
+) -> Windows2dMut<'_, T> {
+    assert!(width > 0, "buffer width must be non-zero");
+    assert!(size.0 > 0 && size.1 > 0, "window size must be non-zero");
+    Windows2dMut {
+        buf,
+        stride: width,
+        size,
+        step,
+        next: (0, 0),
+    }
+}
+
+/// Lends every `h`×`w` window of an image stored row by row in `buf`, `width` elements
+/// per row. Trailing elements not making up a whole row are ignored.
+///
+/// # Panics
+/// Panics if `width`, `h` or `w` is zero.
+pub fn windows2d_mut<T>(
+    buf: &mut [T],
+    width: usize,
+    (h, w): (usize, usize),
+) -> Windows2dMut<'_, T> {
+    windows2d(buf, width, (h, w), (1, 1))
+}
+
+/// Lends non-overlapping `h`×`w` tiles of an image stored row by row in `buf`, `width`
+/// elements per row. Tiles that would not fit within the image are not lent.
+///
+/// # Panics
+/// Panics if `width`, `h` or `w` is zero.
+pub fn tiles_mut<T>(buf: &mut [T], width: usize, (h, w): (usize, usize)) -> Windows2dMut<'_, T> {
+    windows2d(buf, width, (h, w), (h, w))
+}
@@ -0,0 +1,189 @@
+use core::marker::PhantomData;
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::fallible::FallibleLendingIter;
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+use crate::{
+    fallible::{IntoResult, LendedFallibleItem},
+    fn_traits::Mapper,
+    hkt::{extend_lifetime, HKT},
+    lending_iter::LendedItem,
+};
+
+pub struct Map<I, F>
+where
+    I: FallibleLendingIter,
+    F: for<'a> Mapper<'a, LendedFallibleItem<'a, I>>,
+{
+    pub(crate) iter: I,
+    pub(crate) fun: F,
+}
+
+#[gat]
+impl<I, F> FallibleLendingIter for Map<I, F>
+where
+    I: FallibleLendingIter,
+    F: for<'b> Mapper<'b, LendedFallibleItem<'b, I>>,
+{
+    type Item<'a> = <F as Mapper<'a, LendedFallibleItem<'a, I>>>::Output
+        where
+            Self: 'a;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error> {
+        Ok(self.iter.next()?.map(|i| self.fun.call(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Filter<I, F>
+where
+    I: FallibleLendingIter,
+    F: for<'a> FnMut(&LendedFallibleItem<'a, I>) -> bool,
+{
+    pub(crate) iter: I,
+    pub(crate) filter: F,
+}
+
+#[gat]
+impl<I, F> FallibleLendingIter for Filter<I, F>
+where
+    I: FallibleLendingIter,
+    F: for<'b> FnMut(&LendedFallibleItem<'b, I>) -> bool,
+{
+    type Item<'a> = LendedFallibleItem<'a, I>
+        where
+            Self: 'a;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error> {
+        loop {
+            // SAFETY:
+            //  The Item<'a> we are matching on is either dropped or returned by
+            //  the end of this match block, so it never escapes its actual lifetime
+            match unsafe {
+                extend_lifetime::<HKT!(Option<LendedFallibleItem<'_, I>>)>(self.iter.next()?)
+            } {
+                Some(item) if !(self.filter)(&item) => continue,
+                item => return Ok(item),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+pub struct MapErr<I, F>
+where
+    I: FallibleLendingIter,
+{
+    pub(crate) iter: I,
+    pub(crate) fun: F,
+}
+
+#[gat]
+impl<I, F, E> FallibleLendingIter for MapErr<I, F>
+where
+    I: FallibleLendingIter,
+    F: FnMut(I::Error) -> E,
+{
+    type Item<'a> = LendedFallibleItem<'a, I>
+        where
+            Self: 'a;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error> {
+        self.iter.next().map_err(&mut self.fun)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub struct Take<I: FallibleLendingIter> {
+    pub(crate) iter: I,
+    pub(crate) n: usize,
+}
+
+#[gat]
+impl<I: FallibleLendingIter> FallibleLendingIter for Take<I> {
+    type Item<'a> = LendedFallibleItem<'a, I>
+        where
+            Self: 'a;
+    type Error = I::Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error> {
+        if self.n == 0 {
+            return Ok(None);
+        }
+        // Errors do not count towards the `n` items, only successfully lent ones do
+        let item = self.iter.next()?;
+        if item.is_some() {
+            self.n -= 1;
+        }
+        Ok(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.min(self.n),
+            Some(upper.map_or(self.n, |upper| upper.min(self.n))),
+        )
+    }
+}
+
+/// [`LendingIter`] over the `Result`s of a [`FallibleLendingIter`].
+pub struct Results<I: FallibleLendingIter> {
+    pub(crate) iter: I,
+}
+
+#[gat]
+impl<I: FallibleLendingIter> LendingIter for Results<I> {
+    type Item<'a> = Result<LendedFallibleItem<'a, I>, I::Error>
+        where
+            Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        self.iter.next().transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// [`FallibleLendingIter`] over a [`LendingIter`] whose items are `Result`s.
+pub struct Fallible<I, E> {
+    pub(crate) iter: I,
+    pub(crate) phantom: PhantomData<fn() -> E>,
+}
+
+#[gat]
+impl<I, E> FallibleLendingIter for Fallible<I, E>
+where
+    I: LendingIter,
+    for<'b> LendedItem<'b, I>: IntoResult<Err = E>,
+{
+    type Item<'a> = <LendedItem<'a, I> as IntoResult>::Ok
+        where
+            Self: 'a;
+    type Error = E;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error> {
+        self.iter.next().map(IntoResult::into_result).transpose()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,118 @@
+use nougat::{gat, Gat};
+
+use crate::fn_traits;
+
+pub mod adapters;
+
+pub type LendedFallibleItem<'lt, I> = Gat!(<I as FallibleLendingIter>::Item<'lt>);
+
+/// Lending iterator whose [`next`](FallibleLendingIter::next) may fail, e.g. because
+/// it is backed by I/O.
+#[gat]
+pub trait FallibleLendingIter {
+    type Item<'a>
+    where
+        Self: 'a;
+    type Error;
+
+    fn next(&mut self) -> Result<Option<Self::Item<'_>>, Self::Error>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    fn count(mut self) -> Result<usize, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        while self.next()?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn for_each<F>(mut self, mut fun: F) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(Self::Item<'a>),
+    {
+        while let Some(v) = self.next()? {
+            fun(v);
+        }
+        Ok(())
+    }
+
+    fn map<F>(self, fun: F) -> adapters::Map<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> fn_traits::Mapper<'a, LendedFallibleItem<'a, Self>>,
+    {
+        adapters::Map { iter: self, fun }
+    }
+
+    fn filter<F>(self, filter: F) -> adapters::Filter<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(&LendedFallibleItem<'a, Self>) -> bool,
+    {
+        adapters::Filter { iter: self, filter }
+    }
+
+    fn map_err<E, F>(self, fun: F) -> adapters::MapErr<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Error) -> E,
+    {
+        adapters::MapErr { iter: self, fun }
+    }
+
+    fn take(self, n: usize) -> adapters::Take<Self>
+    where
+        Self: Sized,
+    {
+        adapters::Take { iter: self, n }
+    }
+
+    /// Converts this into a [`LendingIter`](crate::lending_iter::LendingIter) over
+    /// `Result` items.
+    fn results(self) -> adapters::Results<Self>
+    where
+        Self: Sized,
+    {
+        adapters::Results { iter: self }
+    }
+}
+
+/// Implemented by [`Result`], so that the `Ok` and `Err` types of a lent item can be named.
+pub trait IntoResult {
+    type Ok;
+    type Err;
+
+    fn into_result(self) -> Result<Self::Ok, Self::Err>;
+}
+
+impl<T, E> IntoResult for Result<T, E> {
+    type Ok = T;
+    type Err = E;
+
+    fn into_result(self) -> Result<T, E> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FallibleLendingIter;
+    use crate::prelude::{IntoLending, LendingIter};
+
+    #[test]
+    fn take_does_not_count_errors() {
+        let items = [Ok(1), Err('a'), Ok(2), Err('b'), Ok(3)];
+        let mut iter = items.into_iter().lending().fallible().take(2);
+        assert_eq!(iter.next(), Ok(Some(1)));
+        assert_eq!(iter.next(), Err('a'));
+        assert_eq!(iter.next(), Ok(Some(2)));
+        assert_eq!(iter.next(), Ok(None));
+    }
+}
@@ -1,3 +1,8 @@
+use nougat::{apply, gat, Gat};
+
+#[gat(Item)]
+use crate::lending_iter::LendingIter;
+
 pub trait Mapper<'a, A: 'a> {
     type Output;
 
@@ -24,6 +29,15 @@ impl<'a, A: 'a, R, F: FnMut(A) -> Option<R>> OptionMapper<'a, A> for F {
     }
 }
 
+pub trait Predicate<'a, A: 'a> {
+    fn call(&mut self, item: &A) -> bool;
+}
+impl<'a, A: 'a, F: FnMut(&A) -> bool> Predicate<'a, A> for F {
+    fn call(&mut self, item: &A) -> bool {
+        (self)(item)
+    }
+}
+
 pub trait Scanner<'a, S: 'a, A: 'a> {
     type Output;
 
@@ -36,3 +50,39 @@ impl<'a, S: 'a, A: 'a, R, F: FnMut(&'a mut S, A) -> Option<R>> Scanner<'a, S, A>
         (self)(state, item)
     }
 }
+
+pub trait ResultMapper<'a, A: 'a, E> {
+    type Output;
+
+    fn call(&mut self, item: A) -> Result<Self::Output, E>;
+}
+impl<'a, A: 'a, E, R, F: FnMut(A) -> Result<R, E>> ResultMapper<'a, A, E> for F {
+    type Output = R;
+
+    fn call(&mut self, item: A) -> Result<Self::Output, E> {
+        (self)(item)
+    }
+}
+
+/// Function of the windows of `N` consecutive items of `I`, whose items do not borrow
+/// from it. Used by [`LendingIter::map_windows`] to name the item and output types of the
+/// function without further generic parameters.
+pub trait WindowMapper<I, const N: usize> {
+    type Item;
+    type Output;
+
+    fn call(&mut self, window: &[Self::Item; N]) -> Self::Output;
+}
+#[apply(Gat!)]
+impl<I, T, R, F, const N: usize> WindowMapper<I, N> for F
+where
+    I: for<'a> LendingIter<Item<'a> = T>,
+    F: FnMut(&[T; N]) -> R,
+{
+    type Item = T;
+    type Output = R;
+
+    fn call(&mut self, window: &[T; N]) -> Self::Output {
+        (self)(window)
+    }
+}
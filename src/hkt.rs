@@ -5,12 +5,12 @@ pub use crate::HKT;
 
 /// Trait representing higher kinded type (HKT) parametrized by a single lifetime.
 /// Implemented by [`HKT!`] macro types, e.g.
-/// ```rust
+/// ```ignore
 ///  HKT!(for<'a> T<'a>)
-/// ````
+/// ```
 ///
 /// Although this should not be used directly, concrete implementors of [`HKT`] are precisely
-/// ```rust
+/// ```ignore
 /// PhantomData<for<'a> fn(&'a ()) -> T<'a>>
 /// ```
 /// so that [`FnOnce<(&'a (),)>::Output`] yields the concrete type for lifetime `'a`.
@@ -49,6 +49,9 @@ macro_rules! HKT {
 
 /// Wrapper around [`core::mem::transmute`] that may only be used to extend lifetimes.
 /// Preferable to transmuting directly.
+///
+/// # Safety
+/// The caller must ensure the value is not used past the end of its actual lifetime `'b`.
 pub unsafe fn extend_lifetime<'a, 'b, T: HKT>(v: T::With<'b>) -> T::With<'a> {
     core::mem::transmute(v)
 }
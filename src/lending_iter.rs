@@ -1,7 +1,10 @@
 use nougat::{gat, Gat};
 
 use crate::{
-    adapters, fn_traits,
+    adapters,
+    constructors::windows::ArrayWindowBuf,
+    fallible::{self, IntoResult},
+    fn_traits,
     hkt::{extend_lifetime, HKT},
 };
 
@@ -19,15 +22,11 @@ pub trait LendingIter {
         (0, None)
     }
 
-    fn count(mut self) -> usize
+    fn count(self) -> usize
     where
         Self: Sized,
     {
-        let mut count = 0;
-        while let Some(_) = self.next() {
-            count += 1;
-        }
-        count
+        self.fold(0, |count, _| count + 1)
     }
 
     fn nth<'a>(&'a mut self, n: usize) -> Option<Self::Item<'a>> {
@@ -78,14 +77,12 @@ pub trait LendingIter {
         adapters::Map { iter: self, fun }
     }
 
-    fn for_each<F>(mut self, mut fun: F)
+    fn for_each<F>(self, mut fun: F)
     where
         Self: Sized,
         F: for<'a> FnMut(Self::Item<'a>),
     {
-        while let Some(v) = self.next() {
-            fun(v);
-        }
+        self.fold((), |(), v| fun(v));
     }
 
     fn filter<F>(self, filter: F) -> adapters::Filter<Self, F>
@@ -216,6 +213,99 @@ pub trait LendingIter {
         adapters::Inspect { iter: self, fun }
     }
 
+    /// Calls `fun` on every window of `N` consecutive items, which are kept in a fixed
+    /// array. Items must not borrow from the iterator, so lent items are usually first
+    /// projected to owned values with [`map`](LendingIter::map). The parameter of `fun` needs
+    /// a type annotation, as in `.map_windows::<3, _>(|w: &[f64; 3]| w.iter().sum::<f64>())`.
+    ///
+    /// Every item is stored twice so that windows stay contiguous without shifting them.
+    ///
+    /// # Panics
+    /// Panics if `N` is zero.
+    fn map_windows<const N: usize, F>(self, fun: F) -> adapters::MapWindows<Self, F, N>
+    where
+        Self: Sized,
+        F: fn_traits::WindowMapper<Self, N>,
+        F::Item: Clone,
+    {
+        assert!(N > 0, "window size must be non-zero");
+        adapters::MapWindows {
+            iter: self,
+            fun,
+            window: ArrayWindowBuf::new(),
+        }
+    }
+
+    fn map_ok<F>(self, fun: F) -> adapters::MapOk<Self, F>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult,
+        F: for<'a> fn_traits::Mapper<'a, <LendedItem<'a, Self> as IntoResult>::Ok>,
+    {
+        adapters::MapOk { iter: self, fun }
+    }
+
+    fn filter_ok<F>(self, filter: F) -> adapters::FilterOk<Self, F>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult,
+        F: for<'a> fn_traits::Predicate<'a, <LendedItem<'a, Self> as IntoResult>::Ok>,
+    {
+        adapters::FilterOk { iter: self, filter }
+    }
+
+    fn filter_map_ok<F>(self, filter: F) -> adapters::FilterMapOk<Self, F>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult,
+        F: for<'a> fn_traits::OptionMapper<'a, <LendedItem<'a, Self> as IntoResult>::Ok>,
+    {
+        adapters::FilterMapOk { iter: self, filter }
+    }
+
+    fn and_then_ok<F>(self, fun: F) -> adapters::AndThenOk<Self, F>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult,
+        F: for<'a> fn_traits::ResultMapper<
+            'a,
+            <LendedItem<'a, Self> as IntoResult>::Ok,
+            <LendedItem<'a, Self> as IntoResult>::Err,
+        >,
+    {
+        adapters::AndThenOk { iter: self, fun }
+    }
+
+    /// Calls `processor` with an iterator over the `Ok` values of this iterator, which
+    /// stops at the first `Err`. Returns that error if there was one, and the result of
+    /// `processor` otherwise.
+    fn process_results<E, R, F>(self, processor: F) -> Result<R, E>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult<Err = E>,
+        F: FnOnce(adapters::ProcessResults<'_, Self, E>) -> R,
+    {
+        let mut error = Ok(());
+        let result = processor(adapters::ProcessResults {
+            iter: self,
+            error: &mut error,
+        });
+        error.map(|()| result)
+    }
+
+    /// Converts an iterator over `Result` items into a
+    /// [`FallibleLendingIter`](fallible::FallibleLendingIter) over the `Ok` values.
+    fn fallible<E>(self) -> fallible::adapters::Fallible<Self, E>
+    where
+        Self: Sized,
+        for<'a> LendedItem<'a, Self>: IntoResult<Err = E>,
+    {
+        fallible::adapters::Fallible {
+            iter: self,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
     fn by_ref(&mut self) -> &mut Self
     where
         Self: Sized,
@@ -223,12 +313,24 @@ pub trait LendingIter {
         self
     }
 
-    fn try_fold(&mut self) {
-        todo!()
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<B, E>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(B, LendedItem<'a, Self>) -> Result<B, E>,
+    {
+        let mut accum = init;
+        while let Some(x) = self.next() {
+            accum = f(accum, x)?;
+        }
+        Ok(accum)
     }
 
-    fn try_for_each(&mut self) {
-        todo!()
+    fn try_for_each<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(LendedItem<'a, Self>) -> Result<(), E>,
+    {
+        self.try_fold((), |(), x| f(x))
     }
 
     fn fold<B, F>(mut self, init: B, mut f: F) -> B
@@ -248,12 +350,8 @@ pub trait LendingIter {
         Self: Sized,
         F: for<'a> FnMut(LendedItem<'a, Self>) -> bool,
     {
-        while let Some(b) = self.next().map(&mut f) {
-            if !b {
-                return false;
-            }
-        }
-        true
+        self.try_fold((), |(), x| if f(x) { Ok(()) } else { Err(()) })
+            .is_ok()
     }
 
     fn any<F>(&mut self, mut f: F) -> bool
@@ -261,12 +359,8 @@ pub trait LendingIter {
         Self: Sized,
         F: for<'a> FnMut(LendedItem<'a, Self>) -> bool,
     {
-        while let Some(b) = self.next().map(&mut f) {
-            if b {
-                return true;
-            }
-        }
-        false
+        self.try_fold((), |(), x| if f(x) { Err(()) } else { Ok(()) })
+            .is_err()
     }
 
     fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item<'_>>
@@ -308,14 +402,8 @@ pub trait LendingIter {
         Self: Sized,
         P: for<'a> FnMut(LendedItem<'a, Self>) -> bool,
     {
-        let mut i = 0usize;
-        while let Some(b) = self.next().map(&mut predicate) {
-            if b {
-                return Some(i);
-            }
-            i += 1;
-        }
-        None
+        self.try_fold(0usize, |i, x| if predicate(x) { Err(i) } else { Ok(i + 1) })
+            .err()
     }
 
     fn copied(self)
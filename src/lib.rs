@@ -2,9 +2,19 @@
 
 pub extern crate nougat;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod adapters;
 pub mod constructors;
+pub mod fallible;
 pub mod fn_traits;
 pub mod hkt;
 pub mod lending_iter;
 pub mod prelude;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "stream")]
+pub mod stream;
@@ -1,7 +1,44 @@
 pub use nougat::gat;
 
-pub use crate::constructors::{empty::empty, from_fn::from_fn, from_iter::IntoLending};
+#[cfg(feature = "alloc")]
+pub use crate::constructors::{
+    backtrack::backtrack,
+    cartesian_product::cartesian_product,
+    combinations::{combinations, combinations_with_replacement},
+    cursor::cursor_mut,
+    partitions::{compositions, partitions},
+    subsets::{subsets, subsets_by_size},
+    tree::{bfs_mut, dfs_mut, Children},
+    utf8::{try_utf8_chunks, utf8_chunks},
+};
+pub use crate::constructors::{
+    cartesian_product::array_cartesian_product,
+    columns::{columns_mut, diagonals_mut},
+    combinations::{array_combinations, array_combinations_with_replacement},
+    empty::empty, frames::frames, from_fn::from_fn, from_iter::IntoLending,
+    pairs::{neighbors_mut, pairs_mut},
+    partitions::{compositions_in, partitions_in},
+    permutations::{lexicographic_permutations, permutations_mut},
+    split::{split_ascii_whitespace_mut, split_mut},
+    windows2d::{tiles_mut, windows2d_mut},
+};
+#[cfg(feature = "std")]
+pub use crate::constructors::{
+    csv::csv_records,
+    frames::read_frames,
+    read::{read_chunks, read_exact_records},
+    utf8::read_utf8,
+    walk_dir::walk_dir,
+};
+#[gat(Item)]
+pub use crate::fallible::FallibleLendingIter;
+pub use crate::fallible::LendedFallibleItem;
 pub use crate::hkt::HKT;
 pub use crate::lending_iter::LendedItem;
 #[gat(Item)]
 pub use crate::lending_iter::LendingIter;
+#[cfg(feature = "stream")]
+pub use crate::stream::LendedStreamItem;
+#[cfg(feature = "stream")]
+#[gat(Item)]
+pub use crate::stream::LendingStream;
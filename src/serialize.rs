@@ -0,0 +1,107 @@
+use core::cell::Cell;
+
+use serde::{
+    ser::{Error, SerializeSeq},
+    Serialize, Serializer,
+};
+
+use crate::lending_iter::{LendedItem, LendingIter};
+
+/// Serializes the items of a [`LendingIter`] as a sequence.
+///
+/// [`Serialize::serialize`] takes `&self`, so the iterator is moved out on the first call;
+/// serializing the same wrapper again fails with a custom error.
+pub struct SerializeLending<I: LendingIter> {
+    iter: Cell<Option<I>>,
+}
+
+impl<I: LendingIter> SerializeLending<I> {
+    pub fn new(iter: I) -> Self {
+        SerializeLending {
+            iter: Cell::new(Some(iter)),
+        }
+    }
+}
+
+impl<I> Serialize for SerializeLending<I>
+where
+    I: LendingIter,
+    for<'a> LendedItem<'a, I>: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut iter = self
+            .iter
+            .take()
+            .ok_or_else(|| S::Error::custom("lending iterator was already serialized"))?;
+        let len = match iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+        let mut seq = serializer.serialize_seq(len)?;
+        while let Some(item) = iter.next() {
+            seq.serialize_element(&item)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_ser_tokens, assert_ser_tokens_error, Token};
+
+    use super::SerializeLending;
+    use crate::prelude::{IntoLending, LendingIter};
+
+    #[test]
+    fn exact_length() {
+        let items = SerializeLending::new((1u32..3).lending());
+        let tokens = [Token::Seq { len: Some(2) }, Token::U32(1), Token::U32(2), Token::SeqEnd];
+        assert_ser_tokens(&items, &tokens);
+    }
+
+    #[test]
+    fn unknown_length() {
+        let items = SerializeLending::new((1u32..4).lending().filter(|x: &u32| *x != 2));
+        let tokens = [Token::Seq { len: None }, Token::U32(1), Token::U32(3), Token::SeqEnd];
+        assert_ser_tokens(&items, &tokens);
+    }
+
+    #[test]
+    fn length_through_adapters() {
+        let items = SerializeLending::new((0u32..).lending().take(2));
+        let tokens = [Token::Seq { len: Some(2) }, Token::U32(0), Token::U32(1), Token::SeqEnd];
+        assert_ser_tokens(&items, &tokens);
+
+        let items = SerializeLending::new((0u32..1).lending().chain((5..6).lending()));
+        let tokens = [Token::Seq { len: Some(2) }, Token::U32(0), Token::U32(5), Token::SeqEnd];
+        assert_ser_tokens(&items, &tokens);
+    }
+
+    #[test]
+    fn lent_items() {
+        let items = SerializeLending::new([1u8, 2, 3].into_iter().array_windows_lending::<2>());
+        let tokens = [
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 2 },
+            Token::U8(1),
+            Token::U8(2),
+            Token::TupleEnd,
+            Token::Tuple { len: 2 },
+            Token::U8(2),
+            Token::U8(3),
+            Token::TupleEnd,
+            Token::SeqEnd,
+        ];
+        assert_ser_tokens(&items, &tokens);
+    }
+
+    #[test]
+    fn serialized_once() {
+        let items = SerializeLending::new((1u32..2).lending());
+        assert_ser_tokens(&items, &[Token::Seq { len: Some(1) }, Token::U32(1), Token::SeqEnd]);
+        assert_ser_tokens_error(&items, &[], "lending iterator was already serialized");
+    }
+}
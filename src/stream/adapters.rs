@@ -0,0 +1,153 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::stream::LendingStream;
+use crate::{
+    fn_traits::Mapper,
+    hkt::{extend_lifetime, HKT},
+    stream::LendedStreamItem,
+};
+
+pub struct Map<S, F>
+where
+    S: LendingStream,
+    F: for<'a> Mapper<'a, LendedStreamItem<'a, S>>,
+{
+    pub(crate) stream: S,
+    pub(crate) fun: F,
+}
+
+#[gat]
+impl<S, F> LendingStream for Map<S, F>
+where
+    S: LendingStream,
+    F: for<'b> Mapper<'b, LendedStreamItem<'b, S>>,
+{
+    type Item<'a> = <F as Mapper<'a, LendedStreamItem<'a, S>>>::Output
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        // SAFETY:
+        // The stream is structurally pinned and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        stream.poll_next(cx).map(|item| item.map(|i| this.fun.call(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pub struct Filter<S, F>
+where
+    S: LendingStream,
+    F: for<'a> FnMut(&LendedStreamItem<'a, S>) -> bool,
+{
+    pub(crate) stream: S,
+    pub(crate) filter: F,
+}
+
+#[gat]
+impl<S, F> LendingStream for Filter<S, F>
+where
+    S: LendingStream,
+    F: for<'b> FnMut(&LendedStreamItem<'b, S>) -> bool,
+{
+    type Item<'a> = LendedStreamItem<'a, S>
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        // SAFETY:
+        // The stream is structurally pinned and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+            // SAFETY:
+            //  The Item<'a> we are matching on is either dropped or returned by
+            //  the end of this match block, so it never escapes its actual lifetime
+            match unsafe {
+                extend_lifetime::<HKT!(Poll<Option<LendedStreamItem<'_, S>>>)>(stream.poll_next(cx))
+            } {
+                Poll::Ready(Some(item)) if !(this.filter)(&item) => continue,
+                poll => return poll,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stream.size_hint().1)
+    }
+}
+
+pub struct Take<S: LendingStream> {
+    pub(crate) stream: S,
+    pub(crate) n: usize,
+}
+
+#[gat]
+impl<S: LendingStream> LendingStream for Take<S> {
+    type Item<'a> = LendedStreamItem<'a, S>
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        // SAFETY:
+        // The stream is structurally pinned and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.n == 0 {
+            return Poll::Ready(None);
+        }
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        let poll = stream.poll_next(cx);
+        if let Poll::Ready(Some(_)) = poll {
+            this.n -= 1;
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.min(self.n),
+            Some(upper.map_or(self.n, |upper| upper.min(self.n))),
+        )
+    }
+}
+
+pub struct Enumerate<S: LendingStream> {
+    pub(crate) stream: S,
+    pub(crate) count: usize,
+}
+
+#[gat]
+impl<S: LendingStream> LendingStream for Enumerate<S> {
+    type Item<'a> = (usize, LendedStreamItem<'a, S>)
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        // SAFETY:
+        // The stream is structurally pinned and never moved out of self
+        let this = unsafe { self.get_unchecked_mut() };
+        let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        stream.poll_next(cx).map(|item| {
+            item.map(|i| {
+                let count = this.count;
+                this.count += 1;
+                (count, i)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
@@ -0,0 +1,36 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use nougat::gat;
+
+#[gat(Item)]
+use crate::stream::LendingStream;
+use crate::lending_iter::{LendedItem, LendingIter};
+
+/// Stream which is always ready, yielding the items of a [`LendingIter`].
+pub struct Iter<I: LendingIter> {
+    iter: I,
+}
+
+impl<I: LendingIter> Unpin for Iter<I> {}
+
+#[gat]
+impl<I: LendingIter> LendingStream for Iter<I> {
+    type Item<'a> = LendedItem<'a, I>
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        Poll::Ready(self.get_mut().iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub fn iter<I: LendingIter>(iter: I) -> Iter<I> {
+    Iter { iter }
+}
@@ -0,0 +1,36 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use nougat::gat;
+
+#[gat(Item)]
+use crate::stream::LendingStream;
+
+/// Adapts a [`Stream`] into a [`LendingStream`] whose items do not borrow from it.
+pub struct FromStream<S: Stream> {
+    stream: S,
+}
+
+#[gat]
+impl<S: Stream> LendingStream for FromStream<S> {
+    type Item<'a> = S::Item
+        where
+            Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>> {
+        // SAFETY:
+        // The stream is structurally pinned and never moved out of self
+        unsafe { self.map_unchecked_mut(|s| &mut s.stream) }.poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pub fn from_stream<S: Stream>(stream: S) -> FromStream<S> {
+    FromStream { stream }
+}
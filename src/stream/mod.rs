@@ -0,0 +1,294 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use nougat::{gat, Gat};
+
+use crate::{
+    fn_traits,
+    hkt::{extend_lifetime, HKT},
+};
+
+pub mod adapters;
+pub mod from_iter;
+pub mod from_stream;
+
+pub use from_iter::iter;
+pub use from_stream::from_stream;
+
+pub type LendedStreamItem<'lt, S> = Gat!(<S as LendingStream>::Item<'lt>);
+
+/// Asynchronous counterpart of [`LendingIter`](crate::lending_iter::LendingIter).
+///
+/// Items may borrow from the stream, so an item must be dropped before the stream
+/// is polled again.
+#[gat]
+pub trait LendingStream {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn poll_next<'a>(self: Pin<&'a mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item<'a>>>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: Some(self) }
+    }
+
+    fn map<F>(self, fun: F) -> adapters::Map<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> fn_traits::Mapper<'a, LendedStreamItem<'a, Self>>,
+    {
+        adapters::Map { stream: self, fun }
+    }
+
+    fn filter<F>(self, filter: F) -> adapters::Filter<Self, F>
+    where
+        Self: Sized,
+        F: for<'a> FnMut(&LendedStreamItem<'a, Self>) -> bool,
+    {
+        adapters::Filter {
+            stream: self,
+            filter,
+        }
+    }
+
+    fn take(self, n: usize) -> adapters::Take<Self>
+    where
+        Self: Sized,
+    {
+        adapters::Take { stream: self, n }
+    }
+
+    fn enumerate(self) -> adapters::Enumerate<Self>
+    where
+        Self: Sized,
+    {
+        adapters::Enumerate {
+            stream: self,
+            count: 0,
+        }
+    }
+}
+
+/// Future returned by [`LendingStream::next`].
+pub struct Next<'s, S: ?Sized> {
+    stream: Option<&'s mut S>,
+}
+
+impl<'s, S> Future for Next<'s, S>
+where
+    S: LendingStream + Unpin + ?Sized,
+{
+    type Output = Option<LendedStreamItem<'s, S>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let stream = self.stream.take().expect("`Next` polled after completion");
+        // SAFETY:
+        // The borrow of the stream is given up once the item is ready, so the item is the
+        // only thing left borrowing the stream for 's
+        let poll = unsafe {
+            extend_lifetime::<HKT!(Poll<Option<LendedStreamItem<'_, S>>>)>(
+                Pin::new(&mut *stream).poll_next(cx),
+            )
+        };
+        if poll.is_pending() {
+            self.stream = Some(stream);
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::{pin, Pin},
+        task::{Context, Poll, Waker},
+    };
+
+    use futures_core::Stream;
+    use nougat::gat;
+
+    use super::{from_stream, iter};
+    use crate::prelude::IntoLending;
+    #[gat(Item)]
+    use crate::stream::LendingStream;
+
+    /// Polls `future` to completion with a waker that does nothing, which is enough for
+    /// sources that are ready again as soon as they are polled after being pending.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// Lends the windows `[i, i + 1]` for `i` in `0..end`, being pending before every item.
+    struct Windows {
+        buf: [u32; 2],
+        next: u32,
+        end: u32,
+        pending: bool,
+        polls: usize,
+    }
+
+    fn windows(end: u32) -> Windows {
+        Windows {
+            buf: [0; 2],
+            next: 0,
+            end,
+            pending: true,
+            polls: 0,
+        }
+    }
+
+    #[gat]
+    impl LendingStream for Windows {
+        type Item<'a> = &'a mut [u32]
+            where
+                Self: 'a;
+
+        fn poll_next<'a>(
+            self: Pin<&'a mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Self::Item<'a>>> {
+            let this = self.get_mut();
+            this.polls += 1;
+            if this.next == this.end {
+                return Poll::Ready(None);
+            }
+            if this.pending {
+                this.pending = false;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.pending = true;
+            this.buf = [this.next, this.next + 1];
+            this.next += 1;
+            Poll::Ready(Some(&mut this.buf))
+        }
+    }
+
+    /// Yields `0..end`, being pending before every item.
+    struct Pending {
+        next: u32,
+        end: u32,
+        pending: bool,
+    }
+
+    impl Stream for Pending {
+        type Item = u32;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            let this = self.get_mut();
+            if this.next == this.end {
+                return Poll::Ready(None);
+            }
+            this.pending = !this.pending;
+            if this.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.next += 1;
+            Poll::Ready(Some(this.next - 1))
+        }
+    }
+
+    #[test]
+    fn next_repolls_pending_source() {
+        let mut stream = windows(3);
+        let mut seen = Vec::new();
+        while let Some(window) = block_on(stream.next()) {
+            seen.push([window[0], window[1]]);
+            window[0] = 100;
+        }
+        assert_eq!(seen, [[0, 1], [1, 2], [2, 3]]);
+        // Every item is preceded by a pending poll, and the end by a final one
+        assert_eq!(stream.polls, 7);
+    }
+
+    #[test]
+    fn map() {
+        let mut stream = windows(4).map(|window: &mut [u32]| window[0] + window[1]);
+        let mut seen = Vec::new();
+        while let Some(sum) = block_on(stream.next()) {
+            seen.push(sum);
+        }
+        assert_eq!(seen, [1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn filter() {
+        let mut stream = windows(6).filter(|window: &&mut [u32]| window[0].is_multiple_of(2));
+        let mut seen = Vec::new();
+        while let Some(window) = block_on(stream.next()) {
+            seen.push(window[0]);
+        }
+        assert_eq!(seen, [0, 2, 4]);
+    }
+
+    #[test]
+    fn take() {
+        let mut stream = windows(10).take(2);
+        assert_eq!(stream.size_hint(), (0, Some(2)));
+        let mut seen = Vec::new();
+        while let Some(window) = block_on(stream.next()) {
+            seen.push(window[1]);
+        }
+        assert_eq!(seen, [1, 2]);
+        // The source is not polled anymore once the limit is reached
+        assert_eq!(stream.stream.polls, 4);
+    }
+
+    #[test]
+    fn enumerate() {
+        let mut stream = windows(3).enumerate();
+        let mut seen = Vec::new();
+        while let Some((i, window)) = block_on(stream.next()) {
+            seen.push((i, window[0]));
+        }
+        assert_eq!(seen, [(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn from_lending_iter() {
+        let mut stream = iter([1, 2, 3, 4].into_iter().array_windows_lending::<3>());
+        assert_eq!(stream.size_hint(), (2, Some(2)));
+        let mut seen = Vec::new();
+        while let Some(window) = block_on(stream.next()) {
+            seen.push(window.to_vec());
+        }
+        assert_eq!(seen, [[1, 2, 3], [2, 3, 4]]);
+    }
+
+    #[test]
+    fn from_futures_stream() {
+        let source = Pending {
+            next: 0,
+            end: 4,
+            pending: false,
+        };
+        let mut stream = from_stream(source)
+            .filter(|&i: &u32| i != 2)
+            .map(|i: u32| i * 10)
+            .enumerate();
+        let mut seen = Vec::new();
+        while let Some(item) = block_on(stream.next()) {
+            seen.push(item);
+        }
+        assert_eq!(seen, [(0, 0), (1, 10), (2, 30)]);
+    }
+}